@@ -0,0 +1,48 @@
+//! Compares pattern lookups against a `HashSet` of hashes with a linear scan over a `Vec`.
+//!
+//! > `cargo bench --bench blocked_servers`
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use minecraft_utils::mojang_api::blocked_servers::sha1_hex;
+use minecraft_utils::mojang_api::BlockedServers;
+
+// Roughly the size of the list currently served by mojang.
+const HASH_COUNT: usize = 7000;
+const ITERATIONS: u32 = 1000;
+const ADDRESS: &str = "play.some.unblocked.example.org";
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let hashes: Vec<String> = (0..HASH_COUNT)
+        .map(|i| sha1_hex(&format!("*.server{}.example.com", i)))
+        .collect();
+    let blocked = BlockedServers::from_hashes(hashes.clone()).unwrap();
+
+    // The same patterns `find_blocked_pattern` checks for a domain.
+    let parts: Vec<&str> = ADDRESS.split('.').collect();
+    let patterns: Vec<String> = std::iter::once(ADDRESS.to_string())
+        .chain((1..parts.len()).map(|i| format!("*.{}", parts[i..].join("."))))
+        .collect();
+
+    let vec_lookup = time(|| {
+        let found = patterns
+            .iter()
+            .any(|pattern| hashes.contains(&sha1_hex(pattern)));
+        black_box(found);
+    });
+    let set_lookup = time(|| {
+        black_box(blocked.is_blocked(black_box(ADDRESS)));
+    });
+
+    println!("Vec<String>     is_blocked: {:?}", vec_lookup);
+    println!("HashSet<String> is_blocked: {:?}", set_lookup);
+}
@@ -1,33 +1,17 @@
 use std::env;
 
-use minecraft_utils::mojang_api::user::validate_username;
-use minecraft_utils::mojang_api::{get_username_uuid, Profile};
+use minecraft_utils::mojang_api::Profile;
 
 fn main() {
     let name_uuid = env::args()
-        .skip(1)
-        .next()
+        .nth(1)
         .expect("username or uuid must be provided as an argument");
 
-    let is_uuid = name_uuid.len() > 16;
-    let uuid = if is_uuid {
-        name_uuid.replace('-', "")
-    } else {
-        validate_username(&name_uuid).expect("invalid username");
-        get_username_uuid(&name_uuid).expect("unable to fetch uuid from username")
-    };
-
-    let profile = Profile::fetch(&uuid).expect("unable to fetch user profile.");
+    let profile = Profile::try_from(name_uuid.as_str()).expect("unable to fetch user profile.");
 
     println!("uuid: {}", profile.id);
     println!("name: {}", profile.name);
-    println!(
-        "skin model: {}",
-        profile.slim_model().then(|| "alex").unwrap_or("steve")
-    );
-    println!("skin url: {}", profile.textures().skin.url);
-    println!(
-        "cape url: {}",
-        profile.textures().cape.as_ref().map_or("", |v| &v.url)
-    );
+    println!("skin model: {}", profile.skin_model());
+    println!("skin url: {}", profile.skin_url().unwrap_or(""));
+    println!("cape url: {}", profile.cape_url().unwrap_or(""));
 }
@@ -1,38 +1,237 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::Ipv6Addr;
+use std::path::Path;
+use std::str::FromStr;
 
+use serde::{Deserialize, Serialize, Serializer};
 use sha1::{Digest, Sha1};
 
 use crate::mojang_api::client::get;
-use crate::mojang_api::error::ApiError;
+#[cfg(feature = "async")]
+use crate::mojang_api::client::get_async;
+use crate::mojang_api::error::{ApiError, InvalidHashError};
 
-/// A list of hashes corresponding to blocked server patterns.
+/// The url of Mojang's Blocked Servers List.
+pub const BLOCKED_SERVERS_URL: &str = "https://sessionserver.mojang.com/blockedservers";
+
+/// A set of hashes corresponding to blocked server patterns.
+///
+/// Serializes as `{"hashes": [...]}`, with the hashes sorted.
+/// Deserializing fails if any hash is not valid, the same as [BlockedServers::from_hashes].
 ///
 /// ## Example
 /// ```rust
 /// use minecraft_utils::mojang_api::BlockedServers;
 ///
 /// // Use our own blocked servers list for demonstration purposes.
-/// let blocked = BlockedServers {
-///     hashes: vec![
-///         // *.example.com
-///         String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
-///         // 192.0.*
-///         String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
-///         // 127.0.0.1
-///         String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
-///     ],
-/// };
+/// let blocked = BlockedServers::from_hashes([
+///     // *.example.com
+///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+///     // 192.0.*
+///     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+///     // 127.0.0.1
+///     String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+/// ]).unwrap();
 ///
 /// // Check if server is blocked
 /// assert!(blocked.is_blocked("127.0.0.1"));
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "RawBlockedServers")]
 pub struct BlockedServers {
     /// Hashes of the block patterns
-    pub hashes: Vec<String>,
+    #[serde(serialize_with = "serialize_sorted")]
+    pub hashes: HashSet<String>,
+}
+
+/// The unvalidated form of [BlockedServers], which deserializing goes through.
+#[derive(Deserialize)]
+struct RawBlockedServers {
+    hashes: Vec<String>,
+}
+
+impl TryFrom<RawBlockedServers> for BlockedServers {
+    type Error = InvalidHashError;
+
+    fn try_from(raw: RawBlockedServers) -> Result<Self, Self::Error> {
+        BlockedServers::from_hashes(raw.hashes)
+    }
+}
+
+/// Serializes the hashes sorted, so the output is the same for the same list.
+fn serialize_sorted<S: Serializer>(hashes: &HashSet<String>, ser: S) -> Result<S::Ok, S::Error> {
+    let mut hashes: Vec<&String> = hashes.iter().collect();
+    hashes.sort_unstable();
+    hashes.serialize(ser)
 }
 
 impl BlockedServers {
+    /// Create a Blocked Servers List from the hashes of the block patterns.
+    ///
+    /// Fails with the first hash which is not valid, see [is_valid_sha1_hex].
+    pub fn from_hashes(hashes: impl IntoIterator<Item = String>) -> Result<Self, InvalidHashError> {
+        let hashes = hashes
+            .into_iter()
+            .map(validate_hash)
+            .collect::<Result<_, _>>()?;
+        Ok(BlockedServers { hashes })
+    }
+
+    /// Create a Blocked Servers List from the hashes which are valid, returning the invalid hashes alongside it.
+    ///
+    /// This is useful for hashes from an untrusted source, where the rejected hashes should be reported rather than failing.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let (blocked, invalid) = BlockedServers::from_iter_with_validation([
+    ///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    ///     String::from("*.example.com"),
+    /// ]);
+    ///
+    /// assert!(blocked.is_blocked("mc.example.com"));
+    /// assert_eq!(invalid, ["*.example.com"]);
+    /// ```
+    pub fn from_iter_with_validation(
+        hashes: impl IntoIterator<Item = String>,
+    ) -> (Self, Vec<String>) {
+        let mut valid = HashSet::new();
+        let mut invalid = Vec::new();
+        for hash in hashes {
+            match validate_hash(hash) {
+                Ok(hash) => {
+                    valid.insert(hash);
+                }
+                Err(InvalidHashError(hash)) => invalid.push(hash),
+            }
+        }
+        (BlockedServers { hashes: valid }, invalid)
+    }
+
+    /// Create a Blocked Servers List from an existing set of hashes.
+    ///
+    /// Invalid hashes, such as uppercase hashes which would never match, are skipped with a warning, see [is_valid_sha1_hex].
+    pub fn with_hash_set(hashes: HashSet<String>) -> Self {
+        hashes.into_iter().collect()
+    }
+
+    /// Returns the number of hashes in the list.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns the number of hashes in the list, the same as [BlockedServers::len].
+    pub fn hash_count(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the number of blocked patterns, each hash corresponds to a single pattern.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let blocked = BlockedServers::from_hashes([
+    ///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    ///     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(blocked.pattern_count(), 2);
+    /// assert_eq!(blocked.hash_count(), 2);
+    /// ```
+    pub fn pattern_count(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns if there are no hashes in the list.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let blocked = BlockedServers::fetch().unwrap();
+    ///
+    /// // Sanity check the freshly fetched list
+    /// assert!(!blocked.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Compute which hashes were added and removed between two lists.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let old = BlockedServers::from_hashes([
+    ///     // *.example.com
+    ///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    ///     // 192.0.*
+    ///     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// ]).unwrap();
+    /// let new = BlockedServers::from_hashes([
+    ///     // 192.0.*
+    ///     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    ///     // 127.0.0.1
+    ///     String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+    /// ]).unwrap();
+    ///
+    /// let diff = BlockedServers::diff(&old, &new);
+    /// assert_eq!(diff.added, vec!["4b84b15bff6ee5796152495a230e45e3d7e947d9"]);
+    /// assert_eq!(diff.removed, vec!["8c7122d652cb7be22d1986f1f30b07fd5108d9c0"]);
+    /// assert_eq!(diff.to_string(), "+1 hashes added, -1 removed");
+    /// ```
+    pub fn diff(old: &BlockedServers, new: &BlockedServers) -> BlockedServersDiff {
+        let mut added: Vec<String> = new.hashes.difference(&old.hashes).cloned().collect();
+        let mut removed: Vec<String> = old.hashes.difference(&new.hashes).cloned().collect();
+        added.sort_unstable();
+        removed.sort_unstable();
+        BlockedServersDiff { added, removed }
+    }
+
+    /// Read a Blocked Servers List in the newline separated format returned by Mojang.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let data = "8c7122d652cb7be22d1986f1f30b07fd5108d9c0\n8c15fb642b3e8f58480df51798382f1016e748eb\n";
+    /// let blocked = BlockedServers::from_reader(data.as_bytes()).unwrap();
+    ///
+    /// assert!(blocked.is_blocked("mc.example.com"));
+    /// ```
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut txt = String::new();
+        reader.read_to_string(&mut txt)?;
+        Ok(parse_hashes(&txt))
+    }
+
+    /// Load a Blocked Servers List from a file in the newline separated format returned by Mojang.
+    ///
+    /// This is the same format written by [BlockedServers::save_to_file].
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        BlockedServers::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Save the Blocked Servers List to a file in the newline separated format returned by Mojang.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let blocked = BlockedServers::fetch().unwrap();
+    /// blocked.save_to_file("blocked_servers.txt").unwrap();
+    ///
+    /// let cached = BlockedServers::load_from_file("blocked_servers.txt").unwrap();
+    /// assert_eq!(cached.len(), blocked.len());
+    /// ```
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write!(writer, "{}", self)?;
+        writer.flush()
+    }
+
     /// Fetch current Blocked Servers List
     ///
     /// ## Example
@@ -44,10 +243,23 @@ impl BlockedServers {
     /// assert!(blocked.is_blocked("mc.playmc.mx"));
     /// ```
     pub fn fetch() -> Result<Self, ApiError> {
-        let res = get("https://sessionserver.mojang.com/blockedservers")?;
-        let txt = res.as_str()?;
-        let lines = txt.lines().map(String::from).collect();
-        Ok(BlockedServers { hashes: lines })
+        BlockedServers::from_url(BLOCKED_SERVERS_URL)
+    }
+
+    /// Fetch a Blocked Servers List in the newline separated format returned by Mojang from any url,
+    /// such as a custom list for a modified server.
+    ///
+    /// Invalid hashes are skipped, the same as [BlockedServers::fetch].
+    pub fn from_url(url: &str) -> Result<Self, ApiError> {
+        let res = get(url)?;
+        Ok(parse_hashes(res.as_str()?))
+    }
+
+    /// Fetch current Blocked Servers List asynchronously
+    #[cfg(feature = "async")]
+    pub async fn fetch_async() -> Result<Self, ApiError> {
+        let res = get_async(BLOCKED_SERVERS_URL).await?;
+        Ok(parse_hashes(&res.text().await?))
     }
 
     /// Check if the supplied address is in the blocklist, and if it is then return the matching pattern.
@@ -57,16 +269,14 @@ impl BlockedServers {
     /// # use minecraft_utils::mojang_api::BlockedServers;
     /// use std::borrow::Cow;
     /// # // Use our own blocked servers list for demonstration purposes.
-    /// # let blocked = BlockedServers {
-    /// #     hashes: vec![
-    /// #         // *.example.com
-    /// #         String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
-    /// #         // 192.0.*
-    /// #         String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
-    /// #         // 127.0.0.1
-    /// #         String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
-    /// #     ],
-    /// # };
+    /// # let blocked = BlockedServers::from_hashes([
+    /// #     // *.example.com
+    /// #     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    /// #     // 192.0.*
+    /// #     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// #     // 127.0.0.1
+    /// #     String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+    /// # ]).unwrap();
     ///
     /// // Using the blocked servers list from the struct example find the matching pattern
     /// assert_eq!(blocked.find_blocked_pattern("mc.example.com"), Some(Cow::from("*.example.com")));
@@ -75,24 +285,33 @@ impl BlockedServers {
     /// assert_eq!(blocked.find_blocked_pattern("127.0.0.2"), None);
     /// ```
     pub fn find_blocked_pattern<'a>(&self, address: &'a str) -> Option<Cow<'a, str>> {
-        let address_parts: Vec<&str> = address.split('.').collect();
-
-        if self.is_pattern_blocked(&address) {
-            return Some(Cow::Borrowed(address));
-        }
+        candidate_patterns(address).find(|pattern| self.is_pattern_blocked(pattern))
+    }
 
-        if is_ipv4(&address_parts) {
-            (1..address_parts.len())
-                .rev()
-                .map(|i| format!("{}.*", address_parts[..i].join(".")))
-                .find(|pattern| self.is_pattern_blocked(&pattern))
-                .map(Cow::Owned)
-        } else {
-            (1..address_parts.len())
-                .map(|i| format!("*.{}", address_parts[i..].join(".")))
-                .find(|pattern| self.is_pattern_blocked(&pattern))
-                .map(Cow::Owned)
-        }
+    /// Find every pattern in the blocklist matching the supplied address, from most to least specific.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// use std::borrow::Cow;
+    ///
+    /// let blocked = BlockedServers::from_hashes([
+    ///     // *.blocked.com
+    ///     String::from("051ff34aaf894b87d10407f27cdd5935b1156c18"),
+    ///     // *.bar.blocked.com
+    ///     String::from("83e1954e130ee4718761b27701306c2a69ecd905"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     blocked.find_all_blocked_patterns("foo.bar.blocked.com"),
+    ///     vec![Cow::from("*.bar.blocked.com"), Cow::from("*.blocked.com")]
+    /// );
+    /// assert!(blocked.find_all_blocked_patterns("example.com").is_empty());
+    /// ```
+    pub fn find_all_blocked_patterns<'a>(&self, address: &'a str) -> Vec<Cow<'a, str>> {
+        candidate_patterns(address)
+            .filter(|pattern| self.is_pattern_blocked(pattern))
+            .collect()
     }
 
     /// Check if the supplied address is in the blocklist.
@@ -109,31 +328,424 @@ impl BlockedServers {
         self.find_blocked_pattern(address).is_some()
     }
 
+    /// Returns the addresses which are blocked.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// # let blocked = BlockedServers::from_hashes([
+    /// #     // *.example.com
+    /// #     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    /// #     // 192.0.*
+    /// #     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// #     // 127.0.0.1
+    /// #     String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+    /// # ]).unwrap();
+    /// // Using the blocked servers list from the struct example find which addresses are blocked
+    /// assert_eq!(
+    ///     blocked.matches_any(&["mc.example.com", "127.0.0.2", "192.0.2.235"]),
+    ///     vec!["mc.example.com", "192.0.2.235"]
+    /// );
+    /// ```
+    pub fn matches_any<'a>(&self, addresses: &[&'a str]) -> Vec<&'a str> {
+        addresses
+            .iter()
+            .copied()
+            .filter(|address| self.is_blocked(address))
+            .collect()
+    }
+
     /// Check if a pattern is in the hashed pattern list.
     ///
     /// ## Example
     /// ```rust
     /// # use minecraft_utils::mojang_api::BlockedServers;
-    /// # let blocked = BlockedServers {
-    /// #     hashes: vec![
-    /// #         // *.example.com
-    /// #         String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
-    /// #         // 192.0.*
-    /// #         String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
-    /// #         // 127.0.0.1
-    /// #         String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
-    /// #     ],
-    /// # };
+    /// # let blocked = BlockedServers::from_hashes([
+    /// #     // *.example.com
+    /// #     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    /// #     // 192.0.*
+    /// #     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// #     // 127.0.0.1
+    /// #     String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+    /// # ]).unwrap();
     /// // Using the blocked servers list from the struct example determine if the pattern is in the blocklist or not.
     /// assert!(blocked.is_pattern_blocked("*.example.com"));
     /// assert!(!blocked.is_pattern_blocked("example.com"));
     /// ```
     pub fn is_pattern_blocked(&self, pattern: &str) -> bool {
-        let hash = format!("{:#02X}", Sha1::digest(pattern.as_bytes())).to_lowercase();
-        self.hashes.contains(&Cow::Owned(hash))
+        self.hashes.contains(&sha1_hex(pattern))
+    }
+
+    /// Determine if a precomputed hex encoded SHA1 hash is in the blocklist.
+    ///
+    /// Returns `false` if the hash is not 40 hex characters long.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let blocked = BlockedServers::from_hashes([
+    ///     // *.example.com
+    ///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    /// ]).unwrap();
+    ///
+    /// assert!(blocked.contains_hash("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"));
+    /// assert!(blocked.contains_hash("8C7122D652CB7BE22D1986F1F30B07FD5108D9C0"));
+    /// assert!(!blocked.contains_hash("*.example.com"));
+    /// ```
+    pub fn contains_hash(&self, hash: &str) -> bool {
+        let hash = hash.to_ascii_lowercase();
+        is_valid_sha1_hex(&hash) && self.hashes.contains(&hash)
+    }
+
+    /// Keeps only the hashes for which the predicate returns `true`.
+    pub fn retain(&mut self, predicate: impl Fn(&str) -> bool) {
+        self.hashes.retain(|hash| predicate(hash));
+    }
+
+    /// Keeps only the hashes of the given patterns, removing every other hash.
+    ///
+    /// Patterns which are not in the list are not added.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::BlockedServers;
+    /// let mut blocked = BlockedServers::from_hashes([
+    ///     // *.example.com
+    ///     String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+    ///     // 192.0.*
+    ///     String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+    /// ]).unwrap();
+    ///
+    /// blocked.retain_patterns(&["*.example.com", "127.0.0.1"]);
+    ///
+    /// assert_eq!(blocked.len(), 1);
+    /// assert!(blocked.is_pattern_blocked("*.example.com"));
+    /// ```
+    pub fn retain_patterns(&mut self, patterns: &[&str]) {
+        let keep: HashSet<String> = patterns.iter().map(|pattern| sha1_hex(pattern)).collect();
+        self.retain(|hash| keep.contains(hash));
     }
 }
 
+/// Parses the newline separated format returned by Mojang.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::BlockedServers;
+/// let blocked: BlockedServers = "8c7122d652cb7be22d1986f1f30b07fd5108d9c0\n".parse().unwrap();
+///
+/// assert!(blocked.is_blocked("mc.example.com"));
+/// // Displaying gives back the same format, sorted.
+/// assert_eq!(blocked.to_string(), "8c7122d652cb7be22d1986f1f30b07fd5108d9c0\n");
+/// ```
+impl FromStr for BlockedServers {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_hashes(s))
+    }
+}
+
+/// Collects hashes into a Blocked Servers List.
+///
+/// Invalid hashes, such as uppercase hashes which would never match, are skipped with a warning, see [is_valid_sha1_hex].
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::BlockedServers;
+/// let blocked: BlockedServers = ["8c7122d652cb7be22d1986f1f30b07fd5108d9c0"].into_iter().collect();
+///
+/// assert!(blocked.is_blocked("mc.example.com"));
+/// ```
+impl FromIterator<String> for BlockedServers {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        BlockedServers {
+            hashes: iter.into_iter().filter_map(validate_hash_or_warn).collect(),
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for BlockedServers {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        iter.into_iter().map(str::to_owned).collect()
+    }
+}
+
+/// Adds hashes to a Blocked Servers List.
+///
+/// Invalid hashes, such as uppercase hashes which would never match, are skipped with a warning, see [is_valid_sha1_hex].
+///
+/// This can be used to apply the `added` hashes of a [BlockedServersDiff].
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::BlockedServers;
+/// // 192.0.*
+/// let mut blocked: BlockedServers = ["8c15fb642b3e8f58480df51798382f1016e748eb"].into_iter().collect();
+/// // *.example.com
+/// blocked.extend(["8c7122d652cb7be22d1986f1f30b07fd5108d9c0"]);
+///
+/// assert!(blocked.is_blocked("192.0.2.1"));
+/// assert!(blocked.is_blocked("mc.example.com"));
+/// ```
+impl Extend<String> for BlockedServers {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        self.hashes
+            .extend(iter.into_iter().filter_map(validate_hash_or_warn));
+    }
+}
+
+impl<'a> Extend<&'a str> for BlockedServers {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(str::to_owned));
+    }
+}
+
+/// Formats the hashes in the newline separated format returned by Mojang, sorted.
+impl fmt::Display for BlockedServers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut hashes: Vec<&String> = self.hashes.iter().collect();
+        hashes.sort_unstable();
+        hashes.iter().try_for_each(|hash| writeln!(f, "{}", hash))
+    }
+}
+
+/// Parses newline separated hashes, skipping invalid hashes with a warning.
+fn parse_hashes(txt: &str) -> BlockedServers {
+    txt.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Checks a hash is valid, see [is_valid_sha1_hex].
+fn validate_hash(hash: String) -> Result<String, InvalidHashError> {
+    if is_valid_sha1_hex(&hash) {
+        Ok(hash)
+    } else {
+        Err(InvalidHashError(hash))
+    }
+}
+
+/// Checks a hash is valid, returning [None] with a warning if it is not.
+fn validate_hash_or_warn(hash: String) -> Option<String> {
+    match validate_hash(hash) {
+        Ok(hash) => Some(hash),
+        Err(InvalidHashError(hash)) => {
+            log::warn!("skipping invalid blocked server hash {:?}", hash);
+            None
+        }
+    }
+}
+
+/// The changes between two Blocked Servers Lists, created with [BlockedServers::diff].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockedServersDiff {
+    /// Hashes which are only in the new list, sorted.
+    pub added: Vec<String>,
+
+    /// Hashes which are only in the old list, sorted.
+    pub removed: Vec<String>,
+}
+
+impl BlockedServersDiff {
+    /// Returns if the lists had no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for BlockedServersDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "+{} hashes added, -{} removed",
+            self.added.len(),
+            self.removed.len()
+        )
+    }
+}
+
+/// A builder for a Blocked Servers List from patterns rather than their hashes, such as for tests.
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::blocked_servers::BlockedServersBuilder;
+///
+/// let blocked = BlockedServersBuilder::new()
+///     .block("*.example.com")
+///     .block("192.168.*")
+///     // 127.0.0.1
+///     .block_hash("4b84b15bff6ee5796152495a230e45e3d7e947d9")
+///     .build();
+///
+/// assert!(blocked.is_blocked("mc.example.com"));
+/// assert!(blocked.is_blocked("192.168.0.1"));
+/// assert!(blocked.is_blocked("127.0.0.1"));
+/// assert!(!blocked.is_blocked("example.org"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockedServersBuilder {
+    hashes: HashSet<String>,
+}
+
+impl BlockedServersBuilder {
+    /// Creates a builder with nothing blocked.
+    pub fn new() -> Self {
+        BlockedServersBuilder::default()
+    }
+
+    /// Blocks a pattern, which is hashed with [sha1_hex].
+    pub fn block(mut self, pattern: &str) -> Self {
+        self.hashes.insert(sha1_hex(pattern));
+        self
+    }
+
+    /// Blocks the pattern with an already computed hash.
+    ///
+    /// The hash is skipped with a warning if it is not valid, see [is_valid_sha1_hex].
+    pub fn block_hash(mut self, hash: &str) -> Self {
+        self.hashes.extend(validate_hash_or_warn(hash.to_owned()));
+        self
+    }
+
+    /// Builds the Blocked Servers List.
+    pub fn build(self) -> BlockedServers {
+        BlockedServers {
+            hashes: self.hashes,
+        }
+    }
+}
+
+/// Every pattern which could block the address, from most to least specific.
+fn candidate_patterns(address: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    std::iter::once(Cow::Borrowed(address)).chain(all_matching_wildcards(address).map(Cow::Owned))
+}
+
+/// Hashes the input with SHA1, returning the digest as 40 lowercase hex characters.
+///
+/// This is how patterns are hashed in the Blocked Servers List.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::blocked_servers::sha1_hex;
+/// assert_eq!(sha1_hex("*.example.com"), "8c7122d652cb7be22d1986f1f30b07fd5108d9c0");
+/// ```
+pub fn sha1_hex(input: &str) -> String {
+    format!("{:x}", Sha1::digest(input.as_bytes()))
+}
+
+/// Checks if a string is a hash as used in the Blocked Servers List, exactly 40 lowercase hex characters.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::blocked_servers::is_valid_sha1_hex;
+/// assert!(is_valid_sha1_hex("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"));
+/// assert!(!is_valid_sha1_hex("8C7122D652CB7BE22D1986F1F30B07FD5108D9C0"));
+/// assert!(!is_valid_sha1_hex("*.example.com"));
+/// ```
+pub fn is_valid_sha1_hex(s: &str) -> bool {
+    s.len() == 40 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Generates every wildcard pattern which would block the address, from the most specific to the least specific.
+///
+/// This does not check against any list of blocked servers, see [BlockedServers::find_all_blocked_patterns] for that.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::blocked_servers::all_matching_wildcards;
+/// let wildcards: Vec<String> = all_matching_wildcards("foo.bar.example.com").collect();
+/// assert_eq!(wildcards, ["*.bar.example.com", "*.example.com", "*.com"]);
+///
+/// let wildcards: Vec<String> = all_matching_wildcards("192.0.2.235").collect();
+/// assert_eq!(wildcards, ["192.0.2.*", "192.0.*", "192.*"]);
+/// ```
+pub fn all_matching_wildcards(address: &str) -> impl Iterator<Item = String> {
+    let address_parts: Vec<&str> = address.split('.').collect();
+
+    let wildcards: Vec<String> = if is_ipv4(&address_parts) {
+        ipv4_wildcards(&address_parts)
+    } else if is_ipv6(address) {
+        ipv6_wildcards(address)
+    } else {
+        (1..address_parts.len())
+            .map(|i| format!("*.{}", address_parts[i..].join(".")))
+            .collect()
+    };
+
+    wildcards.into_iter()
+}
+
+fn ipv4_wildcards(address_parts: &[&str]) -> Vec<String> {
+    (1..address_parts.len())
+        .rev()
+        .map(|i| format!("{}.*", address_parts[..i].join(".")))
+        .collect()
+}
+
+/// Wildcards for each group prefix of the expanded address, such as `2001:db8:*`.
+///
+/// IPv4-mapped addresses are also checked against the patterns of the IPv4 address they contain.
+fn ipv6_wildcards(address: &str) -> Vec<String> {
+    let ip: Ipv6Addr = match address.parse() {
+        Ok(ip) => ip,
+        Err(_) => return Vec::new(),
+    };
+
+    let segments: Vec<String> = ip.segments().iter().map(|s| format!("{:x}", s)).collect();
+    let mut wildcards: Vec<String> = (1..segments.len())
+        .rev()
+        .map(|i| format!("{}:*", segments[..i].join(":")))
+        .collect();
+
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        let ipv4 = ipv4.to_string();
+        let ipv4_parts: Vec<&str> = ipv4.split('.').collect();
+        wildcards.extend(ipv4_wildcards(&ipv4_parts));
+        wildcards.insert(segments.len() - 1, ipv4);
+    }
+
+    wildcards
+}
+
+/// Converts a block pattern into an anchored regular expression matching the same addresses.
+///
+/// A leading `*.` matches one or more subdomains, and a trailing `*` after a `.` or `:` matches the rest of an ip address.
+/// Everything else is matched literally.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::blocked_servers::pattern_to_regex;
+/// assert_eq!(pattern_to_regex("*.example.com"), r"^.+\.example\.com$");
+/// assert_eq!(pattern_to_regex("192.0.*"), r"^192\.0\..+$");
+/// assert_eq!(pattern_to_regex("127.0.0.1"), r"^127\.0\.0\.1$");
+/// ```
+pub fn pattern_to_regex(pattern: &str) -> String {
+    let (prefix, rest) = match pattern.strip_prefix("*.") {
+        Some(rest) => (r".+\.", rest),
+        None => ("", pattern),
+    };
+    let (rest, suffix) = match rest.strip_suffix('*') {
+        Some(rest) if rest.ends_with(['.', ':']) => (rest, ".+"),
+        _ => (rest, ""),
+    };
+
+    let mut regex = String::with_capacity(pattern.len() * 2 + 2);
+    regex.push('^');
+    regex.push_str(prefix);
+    for c in rest.chars() {
+        if r"\.+*?()|[]{}^$".contains(c) {
+            regex.push('\\');
+        }
+        regex.push(c);
+    }
+    regex.push_str(suffix);
+    regex.push('$');
+    regex
+}
+
 #[doc(hidden)]
 /// Test if an address is ipv4 naively to better match how mojang determines if an address is ipv4 or not.
 ///
@@ -147,3 +759,198 @@ pub fn is_ipv4(ip: &[&str]) -> bool {
     // If thare are too many sections, and each octet is a valid u8
     ip.len() == 4 && ip.iter().all(|x| x.parse::<u8>().is_ok())
 }
+
+#[doc(hidden)]
+/// Test if an address is ipv6.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::blocked_servers::is_ipv6;
+/// assert!(is_ipv6("2001:db8::1"));
+/// assert!(is_ipv6("::1"));
+/// assert!(!is_ipv6("mc.example.com"));
+/// assert!(!is_ipv6("192.0.2.235"));
+/// ```
+pub fn is_ipv6(address: &str) -> bool {
+    address.contains(':') && address.parse::<Ipv6Addr>().is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mojang_api::client::MockClient;
+
+    fn patterns(address: &str) -> Vec<String> {
+        candidate_patterns(address).map(Cow::into_owned).collect()
+    }
+
+    #[test]
+    fn invalid_hashes() {
+        assert_eq!(
+            BlockedServers::from_hashes([String::from("not a hash")]).unwrap_err(),
+            InvalidHashError(String::from("not a hash"))
+        );
+
+        let blocked: BlockedServers = "8c7122d652cb7be22d1986f1f30b07fd5108d9c0\nnot a hash\n"
+            .parse()
+            .unwrap();
+        assert_eq!(blocked.len(), 1);
+    }
+
+    #[test]
+    fn every_insert_validates() {
+        let valid = "8c7122d652cb7be22d1986f1f30b07fd5108d9c0";
+        let uppercase = "8C15FB642B3E8F58480DF51798382F1016E748EB";
+
+        let collected: BlockedServers = [valid, uppercase, "*.example.com"].into_iter().collect();
+        assert_eq!(collected.len(), 1);
+
+        let mut extended = BlockedServers::with_hash_set(HashSet::from([
+            String::from(valid),
+            String::from(uppercase),
+        ]));
+        assert_eq!(extended.len(), 1);
+        extended.extend([uppercase, "not a hash"]);
+        assert_eq!(extended.len(), 1);
+
+        let built = BlockedServersBuilder::new()
+            .block_hash(valid)
+            .block_hash(uppercase)
+            .build();
+        assert_eq!(built.len(), 1);
+        assert!(built.is_blocked("mc.example.com"));
+    }
+
+    #[test]
+    fn validated_hashes() {
+        let (blocked, invalid) = BlockedServers::from_iter_with_validation([
+            String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+            String::from("8C15FB642B3E8F58480DF51798382F1016E748EB"),
+            String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+            String::from("4b84b15bff6ee5796152495a230e45e3d7e947"),
+            String::from(""),
+        ]);
+
+        assert_eq!(blocked.len(), 2);
+        assert!(blocked.is_blocked("127.0.0.1"));
+        assert_eq!(
+            invalid,
+            [
+                "8C15FB642B3E8F58480DF51798382F1016E748EB",
+                "4b84b15bff6ee5796152495a230e45e3d7e947",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let blocked = BlockedServers::from_hashes([
+            String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+            String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+        ])
+        .unwrap();
+
+        let json = serde_json::to_string(&blocked).unwrap();
+        assert_eq!(
+            json,
+            r#"{"hashes":["8c15fb642b3e8f58480df51798382f1016e748eb","8c7122d652cb7be22d1986f1f30b07fd5108d9c0"]}"#
+        );
+
+        let parsed: BlockedServers = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.hashes, blocked.hashes);
+    }
+
+    #[test]
+    fn json_rejects_invalid_hashes() {
+        let uppercase = r#"{"hashes":["8C7122D652CB7BE22D1986F1F30B07FD5108D9C0"]}"#;
+        assert!(serde_json::from_str::<BlockedServers>(uppercase).is_err());
+
+        let pattern = r#"{"hashes":["8c7122d652cb7be22d1986f1f30b07fd5108d9c0","*.example.com"]}"#;
+        assert!(serde_json::from_str::<BlockedServers>(pattern).is_err());
+    }
+
+    #[test]
+    fn from_url() {
+        let _guard = MockClient::new()
+            .respond(
+                "http://localhost/blocklist.txt",
+                200,
+                "8c7122d652cb7be22d1986f1f30b07fd5108d9c0\nnot a hash\n",
+            )
+            .install();
+
+        let blocked = BlockedServers::from_url("http://localhost/blocklist.txt").unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert!(blocked.is_blocked("mc.example.com"));
+
+        let err = BlockedServers::from_url("http://localhost/missing.txt").unwrap_err();
+        assert!(err.is_not_found());
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let blocked = BlockedServers::from_hashes([
+            String::from("8c7122d652cb7be22d1986f1f30b07fd5108d9c0"),
+            String::from("8c15fb642b3e8f58480df51798382f1016e748eb"),
+            String::from("4b84b15bff6ee5796152495a230e45e3d7e947d9"),
+        ])
+        .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "minecraft_utils_blocked_servers_{}.txt",
+            std::process::id()
+        ));
+
+        blocked.save_to_file(&path).unwrap();
+        let loaded = BlockedServers::load_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap().hashes, blocked.hashes);
+    }
+
+    #[test]
+    fn ipv6_patterns() {
+        assert_eq!(
+            patterns("2001:db8::1"),
+            vec![
+                "2001:db8::1",
+                "2001:db8:0:0:0:0:0:*",
+                "2001:db8:0:0:0:0:*",
+                "2001:db8:0:0:0:*",
+                "2001:db8:0:0:*",
+                "2001:db8:0:*",
+                "2001:db8:*",
+                "2001:*",
+            ]
+        );
+    }
+
+    #[test]
+    fn ipv6_link_local_and_loopback() {
+        assert!(is_ipv6("fe80::1"));
+        assert_eq!(patterns("fe80::1")[1], "fe80:0:0:0:0:0:0:*");
+        assert_eq!(patterns("fe80::1").last().unwrap(), "fe80:*");
+
+        assert!(is_ipv6("::1"));
+        assert_eq!(patterns("::1")[1], "0:0:0:0:0:0:0:*");
+    }
+
+    #[test]
+    fn ipv6_mapped_ipv4() {
+        let patterns = patterns("::ffff:192.0.2.235");
+        assert_eq!(patterns[7], "0:*");
+        assert_eq!(
+            &patterns[8..],
+            ["192.0.2.235", "192.0.2.*", "192.0.*", "192.*"]
+        );
+
+        // 192.0.*
+        let blocked =
+            BlockedServers::from_hashes([String::from("8c15fb642b3e8f58480df51798382f1016e748eb")])
+                .unwrap();
+        assert_eq!(
+            blocked.find_blocked_pattern("::ffff:192.0.2.235"),
+            Some(Cow::from("192.0.*"))
+        );
+    }
+}
@@ -1,39 +1,726 @@
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::env;
+use std::hash::{BuildHasher, Hasher};
+use std::rc::Rc;
+#[cfg(feature = "async")]
+use std::sync::Mutex;
+use std::sync::{OnceLock, PoisonError, RwLock};
+use std::thread;
+use std::time::Duration;
+
 use crate::mojang_api::error::ApiError;
-use minreq::{Method, Request, Response, URL};
+use minreq::{Method, Proxy, Request, URL};
+use serde::de::DeserializeOwned;
+
+const USER_AGENT: &str = concat!("minecraft_utils", env!("CARGO_PKG_VERSION"));
+
+/// The longest time to wait between retries, in milliseconds.
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+static DEFAULT_CONFIG: RwLock<ClientConfig> = RwLock::new(ClientConfig::new());
+
+/// The environment variable which redirects every request to another server, such as `http://localhost:8080`.
+///
+/// The scheme and host of each request url are replaced with its value, keeping the path and query.
+/// It is read once, when the first request is made.
+pub const API_BASE_ENV: &str = "MOJANG_API_BASE";
+
+static API_BASE: OnceLock<Option<String>> = OnceLock::new();
+
+fn api_base() -> Option<&'static str> {
+    API_BASE
+        .get_or_init(|| {
+            env::var(API_BASE_ENV)
+                .ok()
+                .filter(|base| !base.is_empty())
+                .map(|base| base.trim_end_matches('/').to_string())
+        })
+        .as_deref()
+}
+
+/// Replaces the scheme and host of `url` with `base`.
+fn rebase_url(url: &str, base: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme
+        .find('/')
+        .map_or("", |i| &without_scheme[i..]);
+    format!("{}{}", base, path)
+}
+
+/// Redirects the url to [API_BASE_ENV] if it is set.
+fn resolve_url(url: String) -> String {
+    match api_base() {
+        Some(base) => rebase_url(&url, base),
+        None => url,
+    }
+}
+
+fn new_request<U: Into<URL>>(method: Method, url: U) -> Request {
+    Request::new(method, resolve_url(url.into()))
+}
+
+/// A response from the api, independent of the [HttpClient] which made the request.
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// The status code of the response.
+    pub status_code: i32,
+    /// The reason given for the status code.
+    pub reason_phrase: String,
+    /// The headers of the response, with lowercase names.
+    pub headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Returns the body as a string, failing if it is not valid utf-8.
+    pub fn as_str(&self) -> Result<&str, ApiError> {
+        std::str::from_utf8(&self.body).map_err(|err| minreq::Error::InvalidUtf8InBody(err).into())
+    }
+
+    /// Returns the body as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the body as bytes, consuming the response.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// Sends the requests made by this library.
+///
+/// Requests are sent with minreq, unless a [MockClient] is installed on the current thread.
+trait HttpClient {
+    /// Sends the request to `url`, returning the response whatever its status code is.
+    fn send(&self, url: &str, req: Request) -> Result<Response, ApiError>;
+}
+
+struct MinreqClient;
+
+impl HttpClient for MinreqClient {
+    fn send(&self, _url: &str, req: Request) -> Result<Response, ApiError> {
+        let res = req.send()?;
+        Ok(Response {
+            status_code: res.status_code,
+            reason_phrase: res.reason_phrase.clone(),
+            headers: res.headers.clone(),
+            body: res.into_bytes(),
+        })
+    }
+}
+
+thread_local! {
+    static THREAD_CLIENT: RefCell<Option<Rc<dyn HttpClient>>> = const { RefCell::new(None) };
+}
+
+/// A http client which responds with preset responses instead of making requests, for testing code which uses the api.
+///
+/// Once installed with [MockClient::install], every blocking request made on the current thread gets the response for its url,
+/// until the returned [MockGuard] is dropped. Urls without a response get an empty `404` response.
+///
+/// Async requests are not affected. Urls are matched after being redirected by [API_BASE_ENV].
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::MockClient;
+/// use minecraft_utils::mojang_api::get_username_uuid;
+///
+/// let _guard = MockClient::new()
+///     .respond(
+///         "https://api.mojang.com/users/profiles/minecraft/brecert",
+///         200,
+///         r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }"#,
+///     )
+///     .install();
+///
+/// assert_eq!(get_username_uuid("brecert").unwrap(), "7a8084cd1f444a159bb1eef8d5b535a1");
+/// assert!(get_username_uuid("Notch").unwrap_err().is_not_found());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockClient {
+    responses: HashMap<String, Response>,
+    requested: Rc<RefCell<Vec<String>>>,
+}
+
+impl MockClient {
+    /// Creates a client with no responses.
+    pub fn new() -> Self {
+        MockClient::default()
+    }
+
+    /// Responds to requests for `url` with the status code and body.
+    pub fn respond(
+        mut self,
+        url: impl Into<String>,
+        status: i32,
+        body: impl Into<Vec<u8>>,
+    ) -> Self {
+        let res = Response {
+            status_code: status,
+            reason_phrase: String::new(),
+            headers: HashMap::new(),
+            body: body.into(),
+        };
+        self.responses.insert(url.into(), res);
+        self
+    }
+
+    /// Uses the client for requests made on the current thread, until the guard is dropped.
+    pub fn install(self) -> MockGuard {
+        let requested = Rc::clone(&self.requested);
+        let previous = THREAD_CLIENT.with(|client| client.borrow_mut().replace(Rc::new(self)));
+        MockGuard {
+            previous,
+            requested,
+        }
+    }
+}
+
+impl HttpClient for MockClient {
+    fn send(&self, url: &str, _req: Request) -> Result<Response, ApiError> {
+        self.requested.borrow_mut().push(url.to_string());
+        Ok(self
+            .responses
+            .get(url)
+            .cloned()
+            .unwrap_or_else(|| Response {
+                status_code: 404,
+                reason_phrase: String::from("Not Found"),
+                headers: HashMap::new(),
+                body: Vec::new(),
+            }))
+    }
+}
+
+/// Restores the previous client of the thread when dropped, see [MockClient::install].
+#[must_use = "the mock client is uninstalled when the guard is dropped"]
+pub struct MockGuard {
+    previous: Option<Rc<dyn HttpClient>>,
+    requested: Rc<RefCell<Vec<String>>>,
+}
+
+impl MockGuard {
+    /// Returns the url of every request made with the client so far, in the order they were made.
+    pub fn requested(&self) -> Vec<String> {
+        self.requested.borrow().clone()
+    }
+}
+
+impl Drop for MockGuard {
+    fn drop(&mut self) {
+        THREAD_CLIENT.with(|client| *client.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Sends the request with the client installed on the current thread, or with minreq.
+fn execute(url: &str, req: Request) -> Result<Response, ApiError> {
+    match THREAD_CLIENT.with(|client| client.borrow().clone()) {
+        Some(client) => client.send(url, req),
+        None => MinreqClient.send(url, req),
+    }
+}
+
+/// Configuration for the HTTP requests made to the api.
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::ClientConfig;
+///
+/// // Give up on requests which take longer than 10 seconds.
+/// ClientConfig::set_default(ClientConfig {
+///     timeout_secs: Some(10),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// How long to wait for a response before failing, in seconds.
+    ///
+    /// Requests never time out if this is [None].
+    pub timeout_secs: Option<u64>,
+
+    /// The `User-Agent` header sent with requests, or the library default if [None].
+    pub user_agent: Option<String>,
+
+    /// How many times to retry a request which was rate limited (`429`) or the service was unavailable (`503`).
+    ///
+    /// Retries are only done for blocking `GET` requests.
+    /// `POST` requests are never retried, as some of them are not safe to repeat, such as [refresh](crate::mojang_api::yggdrasil::refresh)
+    /// which invalidates the token it was given.
+    pub max_retries: u8,
+
+    /// How long to wait before the first retry, in milliseconds.
+    ///
+    /// The wait doubles after each retry, up to 30 seconds, with some random jitter applied.
+    pub initial_backoff_ms: u64,
+
+    /// Extra headers sent with every request, as `(name, value)` pairs.
+    pub extra_headers: Vec<(String, String)>,
+
+    /// The proxy to send requests through, such as `http://localhost:8080`.
+    pub proxy: Option<String>,
+
+    /// Accept TLS certificates which are invalid, such as self-signed certificates from a development proxy.
+    ///
+    /// **This is dangerous**, any server can impersonate Mojang's servers and read access tokens while this is enabled.
+    /// Only enable it for development, never in production.
+    ///
+    /// This is only available with the `async` feature and only applies to async requests.
+    /// Blocking requests are made with minreq, which has no way to skip verification, so they always verify certificates.
+    #[cfg(feature = "async")]
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl ClientConfig {
+    /// Creates the default config.
+    pub const fn new() -> Self {
+        ClientConfig {
+            timeout_secs: None,
+            user_agent: None,
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            extra_headers: Vec::new(),
+            proxy: None,
+            #[cfg(feature = "async")]
+            danger_accept_invalid_certs: false,
+        }
+    }
+
+    /// Sets the config used for every request made by this library.
+    pub fn set_default(config: ClientConfig) {
+        *DEFAULT_CONFIG
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = config;
+    }
+
+    /// Returns the config used for every request made by this library.
+    pub fn get_default() -> ClientConfig {
+        DEFAULT_CONFIG
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Applies the config to a request.
+    ///
+    /// Fails if the proxy is not a valid proxy address.
+    pub fn apply_to_request(&self, req: Request) -> Result<Request, ApiError> {
+        let mut req = req.with_header(
+            "User-Agent",
+            self.user_agent.as_deref().unwrap_or(USER_AGENT),
+        );
+
+        for (name, value) in &self.extra_headers {
+            req = req.with_header(name, value);
+        }
+
+        if let Some(timeout) = self.timeout_secs {
+            req = req.with_timeout(timeout);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            req = req.with_proxy(Proxy::new(proxy)?);
+        }
+
+        Ok(req)
+    }
+
+    /// How long to wait before the retry after `attempt` previous retries.
+    fn backoff(&self, attempt: u8) -> Duration {
+        let backoff = self
+            .initial_backoff_ms
+            .saturating_mul(1 << attempt.min(16))
+            .min(MAX_BACKOFF_MS);
+
+        // Wait somewhere between half and all of the backoff, so clients don't retry in lockstep.
+        let jitter = RandomState::new().build_hasher().finish() % (backoff / 2 + 1);
+        Duration::from_millis(backoff - jitter)
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig::new()
+    }
+}
+
+/// Builds a [ClientConfig] for individual requests, such as with [get_with_config].
+///
+/// Anything not set is left as the [ClientConfig] default.
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::ClientBuilder;
+///
+/// let config = ClientBuilder::new()
+///     .timeout_secs(10)
+///     .header("Accept-Language", "en-US")
+///     .proxy("http://localhost:8080")
+///     .build();
+///
+/// assert_eq!(config.timeout_secs, Some(10));
+/// assert_eq!(config.proxy.as_deref(), Some("http://localhost:8080"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientBuilder {
+    timeout_secs: Option<u64>,
+    extra_headers: Vec<(String, String)>,
+    proxy: Option<String>,
+    #[cfg(feature = "async")]
+    danger_accept_invalid_certs: bool,
+}
+
+impl ClientBuilder {
+    /// Creates a builder with nothing set.
+    pub fn new() -> Self {
+        ClientBuilder::default()
+    }
+
+    /// Sets how long to wait for a response before failing, in seconds.
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the proxy to send requests through.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Accepts invalid TLS certificates for async requests, see [ClientConfig::danger_accept_invalid_certs] for why this is dangerous.
+    #[cfg(feature = "async")]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Builds the config.
+    pub fn build(self) -> ClientConfig {
+        ClientConfig {
+            timeout_secs: self.timeout_secs,
+            extra_headers: self.extra_headers,
+            proxy: self.proxy,
+            #[cfg(feature = "async")]
+            danger_accept_invalid_certs: self.danger_accept_invalid_certs,
+            ..ClientConfig::new()
+        }
+    }
+}
 
 #[doc(hidden)]
-pub fn fetch<U: Into<URL>>(method: Method, url: U) -> Request {
-    Request::new(method, url).with_header(
-        "User-Agent",
-        concat!("minecraft_utils", env!("CARGO_PKG_VERSION")),
-    )
+pub fn fetch<U: Into<URL>>(method: Method, url: U) -> Result<Request, ApiError> {
+    ClientConfig::get_default().apply_to_request(new_request(method, url))
 }
 
 #[doc(hidden)]
 pub fn get<U: Into<URL>>(url: U) -> Result<Response, ApiError> {
-    let res = fetch(Method::Get, url).send()?;
-    if res.status_code == 200 {
-        Ok(res)
+    get_with_config(url, &ClientConfig::get_default())
+}
+
+/// Parses the body of a response as json, keeping the body in the error if parsing fails.
+#[doc(hidden)]
+pub fn parse_json<T: DeserializeOwned>(res: &Response) -> Result<T, ApiError> {
+    let body = res.as_str()?;
+    serde_json::from_str(body).map_err(|source| ApiError::Deserialize {
+        body: body.to_string(),
+        source,
+    })
+}
+
+/// Percent-encodes a value for use in a url query, leaving only unreserved characters as they are.
+pub(crate) fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Gets the body of a response as bytes, such as for downloading texture images.
+pub fn get_raw(url: &str) -> Result<Vec<u8>, ApiError> {
+    Ok(get(url)?.into_bytes())
+}
+
+/// Gets from an endpoint using `config` instead of the default config.
+pub fn get_with_config<U: Into<URL>>(url: U, config: &ClientConfig) -> Result<Response, ApiError> {
+    let url = resolve_url(url.into());
+    let req = config.apply_to_request(Request::new(Method::Get, url.as_str()))?;
+    send(&url, req, Method::Get, &[200], config)
+}
+
+#[doc(hidden)]
+pub fn post<U: Into<URL>, T: serde::ser::Serialize>(
+    url: U,
+    body: &T,
+) -> Result<Response, ApiError> {
+    post_with_config(url, body, &ClientConfig::get_default())
+}
+
+/// Posts to an endpoint using `config` instead of the default config.
+pub fn post_with_config<U: Into<URL>, T: serde::ser::Serialize>(
+    url: U,
+    body: &T,
+    config: &ClientConfig,
+) -> Result<Response, ApiError> {
+    let url = resolve_url(url.into());
+    let req = config.apply_to_request(Request::new(Method::Post, url.as_str()))?;
+    send(&url, req.with_json(body)?, Method::Post, &[200], config)
+}
+
+/// Posts to an endpoint which responds with no content (`204`) on success.
+#[doc(hidden)]
+pub fn post_empty<U: Into<URL>, T: serde::ser::Serialize>(
+    url: U,
+    body: &T,
+) -> Result<(), ApiError> {
+    post_empty_with_config(url, body, &ClientConfig::get_default())
+}
+
+/// Posts to an endpoint which responds with no content (`204`) on success, using `config` instead of the default config.
+pub fn post_empty_with_config<U: Into<URL>, T: serde::ser::Serialize>(
+    url: U,
+    body: &T,
+    config: &ClientConfig,
+) -> Result<(), ApiError> {
+    let url = resolve_url(url.into());
+    let req = config.apply_to_request(Request::new(Method::Post, url.as_str()))?;
+    send(
+        &url,
+        req.with_json(body)?,
+        Method::Post,
+        &[200, 204],
+        config,
+    )?;
+    Ok(())
+}
+
+/// Gets from an endpoint which responds with no content (`204`) on success, using `config` instead of the default config.
+pub fn get_empty_with_config<U: Into<URL>>(url: U, config: &ClientConfig) -> Result<(), ApiError> {
+    let url = resolve_url(url.into());
+    let req = config.apply_to_request(Request::new(Method::Get, url.as_str()))?;
+    send(&url, req, Method::Get, &[200, 204], config)?;
+    Ok(())
+}
+
+/// Sends the request, retrying rate limited `GET` requests as configured by `config`.
+fn send(
+    url: &str,
+    req: Request,
+    method: Method,
+    success: &[i32],
+    config: &ClientConfig,
+) -> Result<Response, ApiError> {
+    let max_retries = if matches!(method, Method::Get) {
+        config.max_retries
     } else {
-        Err(ApiError::Request {
+        0
+    };
+    let mut attempt = 0;
+
+    loop {
+        let res = execute(url, req.clone())?;
+        if success.contains(&res.status_code) {
+            return Ok(res);
+        }
+
+        let retry_after = retry_after_secs(&res);
+        if attempt < max_retries && matches!(res.status_code, 429 | 503) {
+            let backoff = match retry_after {
+                Some(secs) => Duration::from_secs(secs).min(Duration::from_millis(MAX_BACKOFF_MS)),
+                None => config.backoff(attempt),
+            };
+            attempt += 1;
+            log::debug!(
+                "request failed with status {}, retrying in {:?} (attempt {} of {})",
+                res.status_code,
+                backoff,
+                attempt,
+                max_retries
+            );
+            thread::sleep(backoff);
+            continue;
+        }
+
+        if res.status_code == 429 {
+            return Err(ApiError::TooManyRequests { retry_after });
+        }
+
+        return Err(ApiError::Request {
             status: res.status_code,
             reason: res.reason_phrase,
-        })
+        });
     }
 }
 
+/// Reads the `Retry-After` header as a number of seconds, ignoring the http date form.
+fn retry_after_secs(res: &Response) -> Option<u64> {
+    res.headers.get("retry-after")?.trim().parse().ok()
+}
+
+/// The client used for async requests and the config it was built with.
+///
+/// Reusing the client keeps its connection pool and TLS sessions between requests,
+/// it is only rebuilt when the default config changes.
+#[cfg(feature = "async")]
+static ASYNC_CLIENT: Mutex<Option<(ClientConfig, reqwest::Client)>> = Mutex::new(None);
+
+/// Returns the cached async client, building a new one if `config` differs from the one it was built with.
+#[cfg(feature = "async")]
+fn async_client(config: &ClientConfig) -> Result<reqwest::Client, ApiError> {
+    let mut cached = ASYNC_CLIENT.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((cached_config, client)) = &*cached {
+        if cached_config == config {
+            return Ok(client.clone());
+        }
+    }
+
+    let mut builder =
+        reqwest::Client::builder().user_agent(config.user_agent.as_deref().unwrap_or(USER_AGENT));
+    if let Some(timeout) = config.timeout_secs {
+        builder = builder.timeout(Duration::from_secs(timeout));
+    }
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    if config.danger_accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    let client = builder.build()?;
+    *cached = Some((config.clone(), client.clone()));
+    Ok(client)
+}
+
+/// Builds an async request with the default config.
+///
+/// Unlike blocking requests, async requests are never retried and are not sent to an installed [MockClient].
+#[cfg(feature = "async")]
 #[doc(hidden)]
-pub fn post<U: Into<URL>, T: serde::ser::Serialize>(
+pub fn fetch_async<U: Into<String>>(
+    method: reqwest::Method,
+    url: U,
+) -> Result<reqwest::RequestBuilder, ApiError> {
+    let config = ClientConfig::get_default();
+    let mut req = async_client(&config)?.request(method, resolve_url(url.into()));
+    for (name, value) in &config.extra_headers {
+        req = req.header(name, value);
+    }
+    Ok(req)
+}
+
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub async fn get_async<U: Into<String>>(url: U) -> Result<reqwest::Response, ApiError> {
+    let res = fetch_async(reqwest::Method::GET, url)?.send().await?;
+    check_async(res)
+}
+
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub async fn post_async<U: Into<String>, T: serde::ser::Serialize>(
     url: U,
     body: &T,
-) -> Result<Response, ApiError> {
-    let res = fetch(Method::Post, url).with_json(body)?.send()?;
-    if res.status_code == 200 {
+) -> Result<reqwest::Response, ApiError> {
+    let res = fetch_async(reqwest::Method::POST, url)?
+        .json(body)
+        .send()
+        .await?;
+    check_async(res)
+}
+
+/// Parses the body of a response as json, keeping the body in the error if parsing fails.
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub async fn parse_json_async<T: DeserializeOwned>(res: reqwest::Response) -> Result<T, ApiError> {
+    let body = res.text().await?;
+    serde_json::from_str(&body).map_err(|source| ApiError::Deserialize { body, source })
+}
+
+#[cfg(feature = "async")]
+fn check_async(res: reqwest::Response) -> Result<reqwest::Response, ApiError> {
+    let status = res.status();
+    if status == reqwest::StatusCode::OK {
         Ok(res)
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = res
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok()?.trim().parse().ok());
+        Err(ApiError::TooManyRequests { retry_after })
     } else {
         Err(ApiError::Request {
-            status: res.status_code,
-            reason: res.reason_phrase,
+            status: status.as_u16().into(),
+            reason: status.canonical_reason().unwrap_or_default().to_string(),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rebase_urls() {
+        assert_eq!(
+            rebase_url(
+                "https://api.mojang.com/users/profiles/minecraft/brecert",
+                "http://localhost:8080"
+            ),
+            "http://localhost:8080/users/profiles/minecraft/brecert"
+        );
+        assert_eq!(
+            rebase_url("https://status.mojang.com/check?a=b", "http://localhost"),
+            "http://localhost/check?a=b"
+        );
+        assert_eq!(
+            rebase_url("https://api.mojang.com", "http://localhost"),
+            "http://localhost"
+        );
+    }
+
+    #[test]
+    fn only_get_is_retried() {
+        let url = "https://api.mojang.com/rate_limited";
+        let guard = MockClient::new().respond(url, 429, "").install();
+        let config = ClientConfig {
+            max_retries: 2,
+            initial_backoff_ms: 0,
+            ..ClientConfig::new()
+        };
+
+        let err = post_with_config(url, &"body", &config).unwrap_err();
+        assert!(err.is_rate_limited());
+        assert_eq!(guard.requested().len(), 1);
+
+        let err = get_with_config(url, &config).unwrap_err();
+        assert!(err.is_rate_limited());
+        assert_eq!(guard.requested().len(), 4);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let config = ClientConfig::new();
+
+        for attempt in 0..=u8::MAX {
+            let backoff = config.backoff(attempt).as_millis() as u64;
+            let expected = (config.initial_backoff_ms << attempt.min(16)).min(MAX_BACKOFF_MS);
+            assert!(backoff <= expected);
+            assert!(backoff >= expected / 2);
+        }
+    }
+}
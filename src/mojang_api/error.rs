@@ -12,11 +12,160 @@ pub enum ApiError {
         reason: String,
     },
 
+    /// When the response has a status code of `429`, because too many requests were made.
+    #[error("[429] API Request failed: Too Many Requests")]
+    TooManyRequests {
+        /// How many seconds to wait before retrying, from the `Retry-After` header
+        retry_after: Option<u64>,
+    },
+
     /// When the request fails to resolve.
     #[error("Fetching failed: {}", .0)]
     Fetch(#[from] minreq::Error),
+
+    /// When json, such as a response body or [Profile::from_json](crate::mojang_api::Profile::from_json)'s input,
+    /// could not be parsed as the expected type.
+    #[error("Parsing response failed: {}", .source)]
+    Deserialize {
+        /// The json which could not be parsed, such as the body of the response
+        body: String,
+        /// The error from parsing the json
+        source: serde_json::Error,
+    },
+
+    /// When a UUID is invalid, so no request was made.
+    #[error("Invalid uuid: {}", .0)]
+    InvalidUuid(#[from] UuidError),
+
+    /// When a username is invalid, so no request was made.
+    #[error("Invalid username: {}", .0)]
+    InvalidUsername(#[from] UsernameError),
+
+    /// When a downloaded texture is not a valid png image.
+    #[error("Downloaded texture was not a valid png image")]
+    InvalidImage,
+
+    /// When the request fails to resolve while using the async api.
+    #[cfg(feature = "async")]
+    #[error("Fetching failed: {}", .0)]
+    AsyncFetch(#[from] reqwest::Error),
 }
 
+impl ApiError {
+    /// Creates an [ApiError::Request] from a status code and reason.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::error::ApiError;
+    /// let err = ApiError::from_status(503, "Service Unavailable");
+    ///
+    /// assert_eq!(err.status_code(), Some(503));
+    /// assert!(err.is_server_error());
+    /// assert!(!err.is_client_error());
+    /// ```
+    pub fn from_status(status: i32, reason: &str) -> Self {
+        ApiError::Request {
+            status,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Returns the status code of the response, or [None] if the request failed to resolve.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::error::ApiError;
+    /// let err = ApiError::Request {
+    ///     status: 404,
+    ///     reason: String::from("Not Found"),
+    /// };
+    ///
+    /// assert_eq!(err.status_code(), Some(404));
+    /// assert!(err.is_not_found());
+    /// assert!(!err.is_rate_limited());
+    /// ```
+    pub fn status_code(&self) -> Option<i32> {
+        match self {
+            ApiError::Request { status, .. } => Some(*status),
+            ApiError::TooManyRequests { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// Returns if the request was rejected for sending too many requests.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::error::ApiError;
+    /// let err = ApiError::TooManyRequests { retry_after: Some(30) };
+    ///
+    /// assert!(err.is_rate_limited());
+    /// assert_eq!(err.status_code(), Some(429));
+    /// ```
+    pub fn is_rate_limited(&self) -> bool {
+        self.status_code() == Some(429)
+    }
+
+    /// Returns if the requested resource could not be found.
+    pub fn is_not_found(&self) -> bool {
+        self.status_code() == Some(404)
+    }
+
+    /// Returns if the response had a client error (`4xx`) status code.
+    pub fn is_client_error(&self) -> bool {
+        matches!(self.status_code(), Some(400..=499))
+    }
+
+    /// Returns if the response had a server error (`5xx`) status code.
+    pub fn is_server_error(&self) -> bool {
+        matches!(self.status_code(), Some(500..=599))
+    }
+
+    /// Returns if no response was received, such as when the network is down or the request timed out.
+    ///
+    /// These are usually worth retrying, unlike errors returned by Mojang.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::error::ApiError;
+    /// let err = ApiError::Fetch(minreq::Error::AddressNotFound);
+    /// assert!(err.is_network_error());
+    ///
+    /// let err = ApiError::from_status(404, "Not Found");
+    /// assert!(!err.is_network_error());
+    /// ```
+    pub fn is_network_error(&self) -> bool {
+        match self {
+            ApiError::Fetch(err) => !matches!(
+                err,
+                minreq::Error::SerdeJsonError(_) | minreq::Error::InvalidUtf8InBody(_)
+            ),
+            #[cfg(feature = "async")]
+            ApiError::AsyncFetch(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+            _ => false,
+        }
+    }
+
+    /// Returns the underlying error from minreq, for logging why a request failed.
+    pub fn network_cause(&self) -> Option<&minreq::Error> {
+        match self {
+            ApiError::Fetch(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<(i32, String)> for ApiError {
+    fn from((status, reason): (i32, String)) -> Self {
+        ApiError::Request { status, reason }
+    }
+}
+
+/// A hash in a Blocked Servers List was not 40 lowercase hex characters.
+#[derive(Error, Debug, PartialEq)]
+#[error("invalid blocked server hash \"{}\"", .0)]
+pub struct InvalidHashError(pub String);
+
 /// Errors which can occur when validating a username fails.
 #[derive(Error, Debug, PartialEq)]
 pub enum UsernameError {
@@ -24,6 +173,10 @@ pub enum UsernameError {
     #[error("username was empty")]
     Empty,
 
+    /// The username was shorter than 3 characters.
+    #[error("username was too short")]
+    TooShort,
+
     /// The username was longer than 16 characters.
     #[error("username was too long")]
     TooLong,
@@ -31,4 +184,61 @@ pub enum UsernameError {
     /// The username contained an invalid character.
     #[error("username contained invalid character '{}'", .0)]
     InvalidCharacter(char),
+
+    /// The username started or ended with an underscore.
+    #[error("username started or ended with an underscore")]
+    InvalidUnderscorePosition,
+}
+
+/// Errors which can occur when parsing a UUID.
+#[derive(Error, Debug, PartialEq)]
+pub enum UuidError {
+    /// The UUID was not 32 characters long without dashes, or 36 characters long with dashes.
+    #[error("uuid had invalid length {}", .0)]
+    InvalidLength(usize),
+
+    /// The UUID contained a character which is not a hex digit.
+    #[error("uuid contained invalid character '{}'", .0)]
+    InvalidCharacter(char),
+
+    /// The UUID had dashes, but not in the `8-4-4-4-12` positions.
+    #[error("uuid had misplaced dashes")]
+    MisplacedDash,
+}
+
+/// Errors which can occur when fetching a profile from a username.
+#[derive(Error, Debug)]
+pub enum ProfileFetchError {
+    /// The username was invalid.
+    #[error("invalid username: {}", .0)]
+    Username(#[from] UsernameError),
+
+    /// Fetching from the api failed.
+    #[error(transparent)]
+    Api(#[from] ApiError),
+}
+
+/// Errors which can occur when parsing a [User](crate::mojang_api::user::User) from a string.
+#[derive(Error, Debug, PartialEq)]
+pub enum UserParseError {
+    /// The string was not in the `name (uuid)` format.
+    #[error("user was not in the format \"name (uuid)\"")]
+    InvalidFormat,
+
+    /// The username was invalid.
+    #[error("invalid username: {}", .0)]
+    InvalidUsername(#[from] UsernameError),
+}
+
+/// Errors which can occur when verifying the signature of a profile.
+#[cfg(feature = "signature")]
+#[derive(Error, Debug)]
+pub enum SignatureError {
+    /// The public key could not be parsed.
+    #[error("invalid public key: {}", .0)]
+    InvalidKey(#[from] rsa::pkcs8::spki::Error),
+
+    /// The signature does not match the signed data.
+    #[error("signature verification failed: {}", .0)]
+    Verification(#[from] rsa::Error),
 }
@@ -6,15 +6,50 @@ pub mod blocked_servers;
 /// Errors used throughout this library.
 pub mod error;
 
+/// Building urls for NameMC pages.
+pub mod namemc;
+
 /// Fetching the profile/textures, or username history of a user.
 pub mod profile;
 
+/// Listing and managing Realms, Mojang's server hosting service.
+pub mod realms;
+
+/// Answering security questions to trust the user's location.
+pub mod security;
+
+/// Server-side authentication using the session server.
+pub mod session;
+
+/// Utilities for working with player skins.
+pub mod skin;
+
+/// The status of Mojang's services.
+pub mod status;
+
 /// Utilities for fetching basic user data, such as resolving a username to a UUID.
 pub mod user;
 
+/// Utilities for formatting and validating UUIDs.
+pub mod uuid;
+
+/// Legacy account authentication using Yggdrasil.
+///
+/// Yggdrasil is deprecated, new code should authenticate with a Microsoft account instead, as migrated accounts can no longer log in through Yggdrasil.
+///
+/// ## Security
+///
+/// [authenticate](yggdrasil::authenticate) and [signout](yggdrasil::signout) send the account's password to Mojang.
+/// Passwords should never be logged or stored, prefer keeping the access token from [AuthResponse](yggdrasil::AuthResponse) and using [refresh](yggdrasil::refresh) and [validate](yggdrasil::validate) with it.
+/// Access tokens grant full access to the account and should be treated as secrets as well.
+pub mod yggdrasil;
+
 #[doc(hidden)]
 pub mod client;
 
 pub use blocked_servers::BlockedServers;
-pub use profile::Profile;
+pub use client::{ClientBuilder, ClientConfig, MockClient};
+pub use profile::{Profile, SkinModel};
 pub use user::get_username_uuid;
+#[cfg(feature = "async")]
+pub use user::get_username_uuid_async;
@@ -0,0 +1,33 @@
+use crate::mojang_api::client::percent_encode;
+use crate::mojang_api::error::UuidError;
+use crate::mojang_api::uuid::format_uuid;
+
+const NAMEMC: &str = "https://namemc.com";
+
+/// Returns the url of a user's NameMC profile.
+///
+/// The UUID can be either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::namemc::profile_url;
+/// assert_eq!(
+///     profile_url("7a8084cd1f444a159bb1eef8d5b535a1").unwrap(),
+///     "https://namemc.com/profile/7a8084cd-1f44-4a15-9bb1-eef8d5b535a1"
+/// );
+/// ```
+pub fn profile_url(uuid: &str) -> Result<String, UuidError> {
+    Ok(format!("{}/profile/{}", NAMEMC, format_uuid(uuid)?))
+}
+
+/// Returns the url of a NameMC search for a username.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::namemc::search_url;
+/// assert_eq!(search_url("brecert"), "https://namemc.com/search?q=brecert");
+/// assert_eq!(search_url("a b"), "https://namemc.com/search?q=a%20b");
+/// ```
+pub fn search_url(username: &str) -> String {
+    format!("{}/search?q={}", NAMEMC, percent_encode(username))
+}
@@ -1,17 +1,44 @@
-use serde::{de, Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::mojang_api::{client::get, error::ApiError};
+use serde::de::{self, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
 
-fn deserialize_textures_entry<'de, D>(ty: D) -> Result<TexturesEntry, D::Error>
+#[cfg(feature = "async")]
+use crate::mojang_api::client::{get_async, parse_json_async};
+#[cfg(feature = "signature")]
+use crate::mojang_api::error::SignatureError;
+use crate::mojang_api::namemc;
+use crate::mojang_api::skin::{self, download_png, PlayerTexture, SkinVariant};
+use crate::mojang_api::user::{get_username_uuid, validate_username, DEFAULT_REQUEST_DELAY};
+use crate::mojang_api::uuid::{strip_uuid_dashes, validate_uuid};
+use crate::mojang_api::{
+    client::{get, get_raw, parse_json},
+    error::{ApiError, ProfileFetchError},
+};
+
+/// The name of the property containing the user's textures.
+const TEXTURES_PROPERTY: &str = "textures";
+
+fn decode_textures_entry(value: &str) -> Result<TexturesEntry, serde_json::Error> {
+    // should be fine
+    let mut buf = [0u8; 768];
+    let len = base64::decode_config_slice(value, base64::STANDARD, &mut buf)
+        .map_err(de::Error::custom)?;
+    serde_json::from_slice(&buf[..len])
+}
+
+fn deserialize_base64<'de, D>(ty: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    // should be fine
-    let mut buf = [0u8; 768];
     let str = String::deserialize(ty)?;
-    let len =
-        base64::decode_config_slice(&str, base64::STANDARD, &mut buf).map_err(de::Error::custom)?;
-    serde_json::from_slice(&buf[..len]).map_err(de::Error::custom)
+    base64::decode(&str).map_err(de::Error::custom)
 }
 
 /// More complex user information
@@ -23,11 +50,11 @@ where
 /// let profile = Profile::fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
 ///
 /// assert_eq!(
-///     profile.textures().skin.url,
+///     profile.textures().unwrap().skin.url,
 ///     "http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"
 /// );
 /// ```
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Profile {
     /// The UUID of the user.
     pub id: String,
@@ -35,57 +62,690 @@ pub struct Profile {
     /// The username of the user.
     pub name: String,
 
-    /// Properties associated with the user.
-    pub properties: [ProfileProperty; 1],
+    /// Properties associated with the user, see [Profile::find_property].
+    pub properties: Vec<ProfileProperty>,
 
-    /// If the account is a legacy account or not.
+    /// If the account is a legacy account or not, see [Profile::is_legacy].
     #[serde(default)]
     pub legacy: bool,
+
+    /// If the account is a demo account or not, see [Profile::is_demo].
+    #[serde(default)]
+    pub demo: bool,
 }
 
 impl Profile {
+    /// Parses a profile from the json returned by the api.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let profile = Profile::from_json(r#"{
+    ///     "id" : "7a8084cd1f444a159bb1eef8d5b535a1",
+    ///     "name" : "brecert",
+    ///     "properties" : [ {
+    ///       "name" : "textures",
+    ///       "value" : "ewogICJ0aW1lc3RhbXAiIDogMTY0MDMyNjE1MTg1OSwKICAicHJvZmlsZUlkIiA6ICI3YTgwODRjZDFmNDQ0YTE1OWJiMWVlZjhkNWI1MzVhMSIsCiAgInByb2ZpbGVOYW1lIiA6ICJicmVjZXJ0IiwKICAidGV4dHVyZXMiIDogewogICAgIlNLSU4iIDogewogICAgICAidXJsIiA6ICJodHRwOi8vdGV4dHVyZXMubWluZWNyYWZ0Lm5ldC90ZXh0dXJlL2I4MTMwMjgyYjgwY2MwODg3MmJmYzg1ODk3NTM1MGFiM2YzZmNkNGIxZDE4NzE3YmZiNWI3YjgzOGZjZTRlYWEiLAogICAgICAibWV0YWRhdGEiIDogewogICAgICAgICJtb2RlbCIgOiAic2xpbSIKICAgICAgfQogICAgfQogIH0KfQ=="
+    ///     } ]
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(profile.name, "brecert");
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, ApiError> {
+        serde_json::from_str(json).map_err(|source| ApiError::Deserialize {
+            body: json.to_string(),
+            source,
+        })
+    }
+
+    /// Serializes the profile to json, in the same format the api uses.
+    ///
+    /// The result can be parsed back with [Profile::from_json].
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("profile serialization is infallible")
+    }
+
+    /// Serializes the profile to pretty printed json.
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).expect("profile serialization is infallible")
+    }
+
+    /// Returns if the account is a legacy account.
+    ///
+    /// Legacy accounts are Minecraft accounts from before Mojang accounts were introduced in 2012, which log in with a username instead of an email.
+    /// They were never migrated to a Mojang or Microsoft account,
+    /// and can no longer log in now that Mojang accounts have been replaced by Microsoft accounts.
+    /// Migrated accounts are not legacy, and support everything a normal account does.
+    ///
+    /// The api only includes this when it is `true`.
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+
+    /// Returns if the account is a demo account.
+    ///
+    /// Demo accounts have not purchased the game, and can only play the time limited demo world.
+    /// They can't join online mode servers, so they rarely show up through the session server.
+    ///
+    /// The api only includes this when it is `true`.
+    pub fn is_demo(&self) -> bool {
+        self.demo
+    }
+
     /// Fetches the user profile.
+    ///
+    /// The UUID can be either with or without dashes, an invalid UUID is returned as [ApiError::InvalidUuid] without making a request.
     pub fn fetch(uuid: &str) -> Result<Self, ApiError> {
+        validate_uuid(uuid)?;
+        let url = format!(
+            "https://sessionserver.mojang.com/session/minecraft/profile/{}",
+            uuid
+        );
+        parse_json(&get(url)?)
+    }
+
+    /// Fetches the profile again, replacing this profile with the latest one.
+    ///
+    /// The profile is left unchanged if fetching fails.
+    pub fn reload(&mut self) -> Result<(), ApiError> {
+        *self = Profile::fetch(&self.id)?;
+        Ok(())
+    }
+
+    /// Returns if the textures were served less than `max_age` ago, using the timestamp of the textures property.
+    ///
+    /// Profiles without textures are never fresh, as there is no timestamp to check.
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        let served_at = match self
+            .find_textures_property()
+            .and_then(|property| property.value.as_ref())
+        {
+            Some(entry) => Duration::from_millis(u64::try_from(entry.timestamp).unwrap_or(0)),
+            None => return false,
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        now.saturating_sub(served_at) < max_age
+    }
+
+    /// Returns if both profiles are for the same user, ignoring any other differences such as textures.
+    ///
+    /// This is the same comparison [Hash] uses.
+    pub fn eq_by_uuid(&self, other: &Profile) -> bool {
+        self.id == other.id
+    }
+
+    /// Fetches the profiles of many users, one request at a time.
+    ///
+    /// Waits [DEFAULT_REQUEST_DELAY] between each request to avoid being rate limited.
+    /// The results are in the same order as `uuids`, and a failed fetch does not stop the remaining fetches.
+    pub fn fetch_batch(uuids: &[&str]) -> Vec<Result<Self, ApiError>> {
+        Profile::fetch_batch_with_delay(uuids, DEFAULT_REQUEST_DELAY)
+    }
+
+    /// Fetches the profiles of many users, waiting `delay` between each request.
+    ///
+    /// See [Profile::fetch_batch].
+    pub fn fetch_batch_with_delay(uuids: &[&str], delay: Duration) -> Vec<Result<Self, ApiError>> {
+        uuids
+            .iter()
+            .enumerate()
+            .map(|(i, uuid)| {
+                if i > 0 {
+                    thread::sleep(delay);
+                }
+                Profile::fetch(uuid)
+            })
+            .collect()
+    }
+
+    /// Fetches the user profile along with the signature of its textures property.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let signed = Profile::fetch_signed("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    ///
+    /// assert_eq!(signed.profile.name, "brecert");
+    /// assert!(!signed.signature.is_empty());
+    /// ```
+    pub fn fetch_signed(uuid: &str) -> Result<SignedProfile, ApiError> {
+        validate_uuid(uuid)?;
+        let url = format!(
+            "https://sessionserver.mojang.com/session/minecraft/profile/{}?unsigned=false",
+            uuid
+        );
+        let res = get(url)?;
+        let profile = parse_json(&res)?;
+        let SignedProperties { properties } = parse_json(&res)?;
+        let property = properties
+            .into_iter()
+            .find(|property| property.name == TEXTURES_PROPERTY)
+            .ok_or_else(|| ApiError::Deserialize {
+                body: res.as_str().unwrap_or_default().to_string(),
+                source: serde_json::Error::custom("missing textures property"),
+            })?;
+
+        Ok(SignedProfile {
+            profile,
+            signature: property.signature,
+            value: property.value,
+        })
+    }
+
+    /// Fetches the user profile asynchronously.
+    #[cfg(feature = "async")]
+    pub async fn fetch_async(uuid: &str) -> Result<Self, ApiError> {
+        validate_uuid(uuid)?;
         let url = format!(
             "https://sessionserver.mojang.com/session/minecraft/profile/{}",
             uuid
         );
-        Ok(get(url)?.json()?)
+        parse_json_async(get_async(url).await?).await
+    }
+
+    /// Fetches the username history of the user, sorted chronologically (oldest first).
+    ///
+    /// **Mojang retired this endpoint in 2022**, so this currently always returns an [ApiError].
+    /// It is kept for servers which still serve the old endpoint, such as through [API_BASE_ENV](crate::mojang_api::client::API_BASE_ENV).
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let history = Profile::fetch_username_history("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    ///
+    /// // The original name is never changed to, so it has no timestamp.
+    /// assert_eq!(history[0].changed_to_at, None);
+    /// ```
+    pub fn fetch_username_history(uuid: &str) -> Result<Vec<UsernameEntry>, ApiError> {
+        validate_uuid(uuid)?;
+        let url = format!("https://api.mojang.com/user/profiles/{}/names", uuid);
+        let history: Vec<UsernameEntry> = parse_json(&get(url)?)?;
+        Ok(UsernameHistory::new(history).into_inner())
+    }
+
+    /// Fetches the user's username history, and returns how long ago they changed to their current name.
+    ///
+    /// Returns [None] if the user never changed their name, or if their username history could not be fetched.
+    /// As Mojang retired the username history endpoint, this currently always returns [None],
+    /// see [Profile::fetch_username_history] and [UsernameHistory::age_since_name_change].
+    pub fn age_since_name_change(&self) -> Option<Duration> {
+        let history = Profile::fetch_username_history(&self.id).ok()?;
+        UsernameHistory::new(history).age_since_name_change()
+    }
+
+    /// Returns the property with the given name, or [None] if the user does not have it.
+    pub fn find_property(&self, name: &str) -> Option<&ProfileProperty> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
     }
 
-    /// Returns texture information of the user.
-    pub fn textures(&self) -> &Textures {
-        &self.properties[0].value.textures
+    /// Returns the textures property, or [None] if the user does not have it.
+    pub fn find_textures_property(&self) -> Option<&ProfileProperty> {
+        self.properties
+            .iter()
+            .find(|property| property.is_textures())
+    }
+
+    /// Returns texture information of the user, or [None] if the profile has no textures property.
+    pub fn textures(&self) -> Option<&Textures> {
+        self.find_textures_property()?
+            .value
+            .as_ref()
+            .map(|entry| &entry.textures)
+    }
+
+    /// Returns the url of the user's skin texture.
+    ///
+    /// This is [None] when the profile has no textures property, which the api omits for some profiles,
+    /// see [Profile::textures]. The game then shows the default skin, see [default_skin](crate::mojang_api::skin::default_skin).
+    pub fn skin_url(&self) -> Option<&str> {
+        self.textures().map(|textures| textures.skin.url.as_str())
+    }
+
+    /// Returns the url of the user's cape texture, or [None] if the user does not have a cape.
+    pub fn cape_url(&self) -> Option<&str> {
+        self.textures()?.cape.as_ref().map(|cape| cape.url.as_str())
+    }
+
+    /// Returns if the user has a cape.
+    pub fn has_cape(&self) -> bool {
+        self.textures().is_some_and(Textures::cape_exists)
+    }
+
+    /// Returns the base64 encoded textures property, as returned by the api.
+    pub fn textures_raw(&self) -> Option<&str> {
+        self.find_textures_property()
+            .map(|property| property.value_raw.as_str())
+    }
+
+    /// Downloads the user's skin and cape textures.
+    ///
+    /// A cape which Mojang no longer serves is treated as the user not having a cape,
+    /// and [ApiError::InvalidImage] is returned if either texture is not a png image.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let profile = Profile::fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    /// let textures = profile.download_textures().unwrap();
+    ///
+    /// assert!(textures.skin.is_some());
+    /// ```
+    pub fn download_textures(&self) -> Result<PlayerTexture, ApiError> {
+        let textures = match self.textures() {
+            Some(textures) => textures,
+            None => return Ok(PlayerTexture::default()),
+        };
+
+        let skin = download_png(&textures.skin.url)?;
+        let cape = match &textures.cape {
+            Some(cape) => match download_png(&cape.url) {
+                Ok(png) => Some(png),
+                Err(err) if err.is_not_found() => None,
+                Err(err) => return Err(err),
+            },
+            None => None,
+        };
+
+        Ok(PlayerTexture {
+            skin: Some(skin),
+            cape,
+        })
+    }
+
+    /// Downloads the user's skin texture and returns its dimensions and format.
+    ///
+    /// [None] is returned if the profile has no textures property,
+    /// and [ApiError::InvalidImage] if the downloaded texture is not a png image.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use minecraft_utils::mojang_api::Profile;
+    /// use minecraft_utils::mojang_api::skin::SkinFormat;
+    ///
+    /// let profile = Profile::fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    /// let variant = profile.skin_variant_from_url().unwrap().unwrap();
+    ///
+    /// assert_eq!(variant.format, SkinFormat::Modern);
+    /// ```
+    pub fn skin_variant_from_url(&self) -> Result<Option<SkinVariant>, ApiError> {
+        let textures = match self.textures() {
+            Some(textures) => textures,
+            None => return Ok(None),
+        };
+        let png = download_png(&textures.skin.url)?;
+        SkinVariant::from_png(&png)
+            .map(Some)
+            .ok_or(ApiError::InvalidImage)
+    }
+
+    /// Returns the model of the user's skin.
+    ///
+    /// Profiles without textures are treated as having the classic model.
+    pub fn skin_model(&self) -> SkinModel {
+        let kind = self
+            .textures()
+            .and_then(|textures| textures.skin.metadata.as_ref())
+            .map(SkinMetadata::model_kind);
+
+        if kind == Some(SkinModelKind::Slim) {
+            SkinModel::Slim
+        } else {
+            SkinModel::Classic
+        }
     }
 
     /// Returns if the model of the user is slim or not.
+    #[deprecated(note = "use `Profile::skin_model` instead")]
     pub fn slim_model(&self) -> bool {
-        let is_slim = self
-            .textures()
-            .skin
-            .metadata
-            .as_ref()
-            .map(|m| m.model == "slim");
+        self.skin_model() == SkinModel::Slim
+    }
+
+    /// Returns the url of the user's NameMC profile, see [namemc::profile_url].
+    ///
+    /// Profiles from the api always have a valid id, but if the id is not a valid UUID then a NameMC search for the name is returned instead.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::Profile;
+    /// let profile = Profile::from_json(r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert", "properties": [] }"#).unwrap();
+    ///
+    /// assert_eq!(profile.profile_url(), "https://namemc.com/profile/7a8084cd-1f44-4a15-9bb1-eef8d5b535a1");
+    /// ```
+    pub fn profile_url(&self) -> String {
+        namemc::profile_url(&self.id).unwrap_or_else(|_| namemc::search_url(&self.name))
+    }
+
+    /// Returns the url of an image of the user's face on Crafatar, see [skin::crafatar_head_url].
+    pub fn crafatar_head_url(&self, size: u32) -> String {
+        skin::crafatar_head_url(&self.id, size)
+    }
+
+    /// Returns the url of a 3d render of the user's body on Crafatar, see [skin::crafatar_body_url].
+    pub fn crafatar_body_url(&self, scale: u32) -> String {
+        skin::crafatar_body_url(&self.id, scale)
+    }
+}
+
+/// Fetches the profile of the user currently using the username.
+///
+/// This validates the username, resolves it to a UUID, then fetches the profile.
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::profile::fetch_profile_by_username;
+///
+/// let profile = fetch_profile_by_username("brecert").unwrap();
+///
+/// assert_eq!(profile.id, "7a8084cd1f444a159bb1eef8d5b535a1");
+/// ```
+pub fn fetch_profile_by_username(username: &str) -> Result<Profile, ProfileFetchError> {
+    validate_username(username)?;
+    let uuid = get_username_uuid(username)?;
+    Ok(Profile::fetch(&uuid)?)
+}
+
+/// Fetches a profile from either a UUID or a username.
+///
+/// Strings longer than 16 characters (the longest a username can be) are treated as UUIDs,
+/// and anything else is resolved as a username with [fetch_profile_by_username].
+///
+/// ## Example
+/// ```rust
+/// use minecraft_utils::mojang_api::Profile;
+///
+/// let by_name = Profile::try_from("brecert").unwrap();
+/// let by_uuid = Profile::try_from("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1").unwrap();
+///
+/// assert_eq!(by_name.id, by_uuid.id);
+/// ```
+impl TryFrom<&str> for Profile {
+    type Error = ProfileFetchError;
+
+    fn try_from(name_or_uuid: &str) -> Result<Self, Self::Error> {
+        if name_or_uuid.replace('-', "").len() > 16 {
+            Ok(Profile::fetch(name_or_uuid)?)
+        } else {
+            fetch_profile_by_username(name_or_uuid)
+        }
+    }
+}
+
+/// Hashes only the UUID of the user, so profiles of the same user fetched at different times hash the same.
+impl Hash for Profile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let model = match self.skin_model() {
+            SkinModel::Classic => "classic",
+            SkinModel::Slim => "slim",
+        };
+        write!(f, "{} ({}) [{}]", self.name, self.id, model)
+    }
+}
+
+/// An in-memory cache of profiles, which are refetched after a time to live has passed.
+///
+/// UUIDs are looked up the same with or without dashes, and in any case.
+/// Expired profiles are only evicted when they are next looked up.
+/// If a capacity is set, the least recently used profile is evicted to make room for new ones.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use minecraft_utils::mojang_api::profile::ProfileCache;
+///
+/// let mut cache = ProfileCache::new(Duration::from_secs(60));
+///
+/// // Only the first lookup makes a request.
+/// let profile = cache.get_or_fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+/// assert_eq!(profile.name, "brecert");
+/// let profile = cache.get_or_fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+/// assert_eq!(profile.name, "brecert");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProfileCache {
+    ttl: Duration,
+    capacity: Option<usize>,
+    inner: HashMap<String, (Profile, Instant)>,
+    /// The cached UUIDs, from least to most recently used.
+    order: VecDeque<String>,
+}
+
+impl ProfileCache {
+    /// Creates a cache where profiles expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        ProfileCache {
+            ttl,
+            capacity: None,
+            inner: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Creates a cache where profiles expire after `ttl`, holding at most `capacity` profiles.
+    ///
+    /// A capacity of `0` is treated as `1`, so a fetched profile can always be returned from the cache.
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        ProfileCache {
+            capacity: Some(capacity.max(1)),
+            ..ProfileCache::new(ttl)
+        }
+    }
 
-        matches!(is_slim, Some(true))
+    /// Returns the cached profile, or fetches it if it is not cached or has expired.
+    pub fn get_or_fetch(&mut self, uuid: &str) -> Result<&Profile, ApiError> {
+        let key = cache_key(uuid);
+        if self.get(&key).is_none() {
+            let profile = Profile::fetch(uuid)?;
+            self.insert_with_key(key.clone(), profile);
+        }
+        Ok(&self.inner[&key].0)
+    }
+
+    /// Returns the cached profile, or [None] if it is not cached or has expired.
+    pub fn get(&mut self, uuid: &str) -> Option<&Profile> {
+        let key = cache_key(uuid);
+        let expired = self.inner.get(&key)?.1.elapsed() >= self.ttl;
+        if expired {
+            self.remove(&key);
+            return None;
+        }
+
+        self.touch(&key);
+        self.inner.get(&key).map(|(profile, _)| profile)
+    }
+
+    /// Caches a profile under its id, replacing any previously cached profile.
+    pub fn insert(&mut self, profile: Profile) {
+        self.insert_with_key(cache_key(&profile.id), profile);
+    }
+
+    /// Removes a profile from the cache, returning it if it was cached.
+    pub fn remove(&mut self, uuid: &str) -> Option<Profile> {
+        let uuid = cache_key(uuid);
+        let (profile, _) = self.inner.remove(&uuid)?;
+        self.order.retain(|key| *key != uuid);
+        Some(profile)
+    }
+
+    /// Returns the number of cached profiles, including expired profiles which have not been evicted yet.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns if there are no cached profiles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Removes every profile from the cache.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.order.clear();
+    }
+
+    fn insert_with_key(&mut self, uuid: String, profile: Profile) {
+        if self.inner.contains_key(&uuid) {
+            self.touch(&uuid);
+        } else {
+            self.order.push_back(uuid.clone());
+        }
+        self.inner.insert(uuid, (profile, Instant::now()));
+
+        if let Some(capacity) = self.capacity {
+            while self.inner.len() > capacity {
+                match self.order.pop_front() {
+                    Some(oldest) => self.inner.remove(&oldest),
+                    None => break,
+                };
+            }
+        }
+    }
+
+    /// Marks the profile as the most recently used.
+    fn touch(&mut self, uuid: &str) {
+        if let Some(i) = self.order.iter().position(|key| key == uuid) {
+            if let Some(key) = self.order.remove(i) {
+                self.order.push_back(key);
+            }
+        }
     }
 }
 
-/// A property associated with the user, currently only supports textures.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// Normalizes a UUID into the key it is cached under, without dashes and in lowercase.
+///
+/// Strings which are not UUIDs are only lowercased.
+fn cache_key(uuid: &str) -> String {
+    strip_uuid_dashes(uuid).unwrap_or_else(|_| uuid.to_ascii_lowercase())
+}
+
+/// A [Profile] along with Mojang's signature of its textures property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedProfile {
+    /// The profile of the user.
+    pub profile: Profile,
+
+    /// The signature of the textures property.
+    pub signature: Vec<u8>,
+
+    /// The base64 encoded textures property the signature was made for.
+    value: String,
+}
+
+impl SignedProfile {
+    /// Verifies the signature of the textures property using Mojang's public key.
+    ///
+    /// The public key is expected to be DER encoded, such as the `yggdrasil_session_pubkey.der` bundled with the game.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let public_key = std::fs::read("yggdrasil_session_pubkey.der").unwrap();
+    /// let signed = Profile::fetch_signed("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    ///
+    /// assert!(signed.verify_signature(&public_key).is_ok());
+    /// ```
+    #[cfg(feature = "signature")]
+    pub fn verify_signature(&self, public_key: &[u8]) -> Result<(), SignatureError> {
+        use rsa::pkcs8::DecodePublicKey;
+        use rsa::{Pkcs1v15Sign, RsaPublicKey};
+        use sha1::{Digest, Sha1};
+
+        let key = RsaPublicKey::from_public_key_der(public_key)?;
+        let hashed = Sha1::digest(self.value.as_bytes());
+        key.verify(Pkcs1v15Sign::new::<Sha1>(), &hashed, &self.signature)?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct SignedProperties {
+    properties: Vec<SignedProperty>,
+}
+
+#[derive(Deserialize)]
+struct SignedProperty {
+    name: String,
+
+    value: String,
+
+    #[serde(deserialize_with = "deserialize_base64")]
+    signature: Vec<u8>,
+}
+
+/// A property associated with the user, only the textures property is decoded.
+///
+/// (De)serializes as the `value` being base64 encoded, the same as the api.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "RawProfileProperty", into = "RawProfileProperty")]
 pub struct ProfileProperty {
     /// Name of the property.
     pub name: String,
 
-    /// The value property.
-    #[serde(deserialize_with = "deserialize_textures_entry")]
-    pub value: TexturesEntry,
+    /// The decoded value of the property, or [None] if it is not the textures property.
+    pub value: Option<TexturesEntry>,
+
+    /// The value property as returned by the api, before being decoded.
+    pub value_raw: String,
+}
+
+impl ProfileProperty {
+    /// Returns if this is the textures property, which is the only property decoded into [ProfileProperty::value].
+    pub fn is_textures(&self) -> bool {
+        self.name == TEXTURES_PROPERTY
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawProfileProperty {
+    name: String,
+    value: String,
+}
+
+impl TryFrom<RawProfileProperty> for ProfileProperty {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawProfileProperty) -> Result<Self, Self::Error> {
+        let value = if raw.name == TEXTURES_PROPERTY {
+            Some(decode_textures_entry(&raw.value)?)
+        } else {
+            None
+        };
+
+        Ok(ProfileProperty {
+            name: raw.name,
+            value,
+            value_raw: raw.value,
+        })
+    }
+}
+
+impl From<ProfileProperty> for RawProfileProperty {
+    fn from(property: ProfileProperty) -> Self {
+        RawProfileProperty {
+            name: property.name,
+            value: property.value_raw,
+        }
+    }
 }
 
 /// A texture entry in the properties.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct TexturesEntry {
-    /// When the entry was served.
+    /// When the entry was served, as a unix timestamp (in ms).
     pub timestamp: i64,
 
     /// UUID of the user.
@@ -100,10 +760,32 @@ pub struct TexturesEntry {
     pub textures: Textures,
 }
 
+impl TexturesEntry {
+    /// Encodes the entry as base64 json, the format of the value of a textures [ProfileProperty].
+    ///
+    /// This is the reverse of how the entry is decoded when deserializing a profile.
+    pub fn to_base64(&self) -> String {
+        let json = serde_json::to_vec(self).expect("textures entry serialization is infallible");
+        base64::encode(json)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TexturesEntry {
+    /// Returns when the entry was served.
+    ///
+    /// Returns [None] if the timestamp is outside of the range `chrono` can represent.
+    pub fn timestamp_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone;
+
+        chrono::Utc.timestamp_millis_opt(self.timestamp).single()
+    }
+}
+
 /// Texture information for the user.
 ///
 /// If the user does not have a cape texture then it will be [None].
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Textures {
     /// Information on the skin, such as the texture url, and model the skin uses.
     #[serde(rename = "SKIN")]
@@ -114,8 +796,35 @@ pub struct Textures {
     pub cape: Option<CapeData>,
 }
 
+impl Textures {
+    /// Returns if the user has a cape.
+    pub fn cape_exists(&self) -> bool {
+        self.cape.is_some()
+    }
+
+    /// Returns if the user's skin is not one of the default skins, see [skin::is_default_skin_url].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::profile::{SkinData, Textures};
+    /// let textures = Textures {
+    ///     skin: SkinData {
+    ///         url: String::from("http://textures.minecraft.net/texture/31f477eb1a7beee631c2ca64d06f8f68fa93a3386d04452ab27f43acdf1b60cb"),
+    ///         metadata: None,
+    ///     },
+    ///     cape: None,
+    /// };
+    ///
+    /// assert!(!textures.has_custom_skin());
+    /// assert!(!textures.cape_exists());
+    /// ```
+    pub fn has_custom_skin(&self) -> bool {
+        !skin::is_default_skin_url(&self.skin.url)
+    }
+}
+
 /// Information relating to the skin of a user.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SkinData {
     /// The url of the skin texture.
     pub url: String,
@@ -124,24 +833,186 @@ pub struct SkinData {
     pub metadata: Option<SkinMetadata>,
 }
 
+impl SkinData {
+    /// Returns the texture id of the skin, see [texture_id_from_url].
+    pub fn texture_id(&self) -> Option<&str> {
+        texture_id_from_url(&self.url)
+    }
+
+    /// Downloads the skin texture as a png image.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use minecraft_utils::mojang_api::Profile;
+    ///
+    /// let profile = Profile::fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+    /// let png = profile.textures().unwrap().skin.download_texture().unwrap();
+    ///
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    pub fn download_texture(&self) -> Result<Vec<u8>, ApiError> {
+        get_raw(&self.url)
+    }
+}
+
 /// Information relating to the cape of a user.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct CapeData {
     /// The url of the cape texture.
     pub url: String,
 }
 
+impl CapeData {
+    /// Returns the texture id of the cape, see [texture_id_from_url].
+    ///
+    /// The name of the cape can be looked up with [cape_name].
+    pub fn cape_id(&self) -> Option<&str> {
+        texture_id_from_url(&self.url)
+    }
+}
+
+/// Extracts the texture id (the hash at the end of the url) from a Mojang texture url.
+///
+/// Returns [None] if the url is not a `textures.minecraft.net` texture url, or the id is not hexadecimal.
+/// Ids are up to 64 characters long, some older textures have shorter ids as leading zeros are dropped.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::profile::texture_id_from_url;
+/// assert_eq!(
+///     texture_id_from_url("http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"),
+///     Some("b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa")
+/// );
+/// assert_eq!(texture_id_from_url("https://example.com/texture/b813"), None);
+/// ```
+pub fn texture_id_from_url(url: &str) -> Option<&str> {
+    let id = url
+        .strip_prefix("http://textures.minecraft.net/texture/")
+        .or_else(|| url.strip_prefix("https://textures.minecraft.net/texture/"))?;
+
+    let is_valid = !id.is_empty() && id.len() <= 64 && id.chars().all(|ch| ch.is_ascii_hexdigit());
+    is_valid.then_some(id)
+}
+
+/// Builds the canonical texture url for a texture id.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::profile::texture_url_from_id;
+/// assert_eq!(
+///     texture_url_from_id("b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"),
+///     "https://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"
+/// );
+/// ```
+pub fn texture_url_from_id(id: &str) -> String {
+    format!("https://textures.minecraft.net/texture/{}", id)
+}
+
+/// Builds the canonical texture url for a cape id, the same as [texture_url_from_id].
+pub fn cape_url_from_id(id: &str) -> String {
+    texture_url_from_id(id)
+}
+
+/// Returns the display name of a cape given by Mojang, or [None] if the cape id is not known.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::profile::cape_name;
+/// assert_eq!(
+///     cape_name("953cac8b779fe41383e675ee2b86071a71658f2180f56fbce8aa315ea70e2ed6"),
+///     Some("Minecon 2011")
+/// );
+/// assert_eq!(cape_name("b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"), None);
+/// ```
+pub fn cape_name(id: &str) -> Option<&'static str> {
+    let name = match id {
+        "2340c0e03dd24a11b15a8b33c2a7e9e32abb2051b2481d0ba7defd635ca7a933" => "Migrator",
+        "953cac8b779fe41383e675ee2b86071a71658f2180f56fbce8aa315ea70e2ed6" => "Minecon 2011",
+        "a2e8d97ec79100e90a75d369d1b3ba81273c4f82bc1b737e934eed4a854be1b6" => "Minecon 2012",
+        "153b1a0dfcbae953cdeb6f2c2bf6bf79943239b1372780da44bcbb29273131da" => "Minecon 2013",
+        "b0cc08840700447322d953a02b965f1d65a13a603bf64b17c803c21446fe1635" => "Minecon 2015",
+        "e7dfea16dc83c97df01a12fabbd1216359c0cd0ea42f9999b6e97c584963e980" => "Minecon 2016",
+        "5786fe99be377dfb6858859f926c4dbc995751e91cee373468c5fbf4865e7151" => "Mojang",
+        "8f120319222a9f4a104e2f5cb97b2cda93199a2ee9e1585cb8d09d6f687cb761" => "Mojang Classic",
+        "9e507afc56359978a3eb3e32367042b853cddd0995d17d0da995662913fb00f7" => "Mojang Studios",
+        "1bf91499701404e21bd46b0191d63239a4ef76ebde88d27e4d430ac211df681e" => "Translator",
+        "ae677f7d98ac70a533713518416df4452fe5700365c09cf45d0d156ea9396551" => "Mojira Moderator",
+        "17912790ff164b93196f08ba71d0e62129304776d0f347334f8a6eae509f8a56" => "Realms Mapmaker",
+        "ca35c56efe71ed290385f4ab5346a1826b546a54d519e6a3ff01efa01acce81" => "Cobalt",
+        "3efadf6510961830f9fcc077f19b4daf286d502b5f5aafbd807c7bbffcaca245" => "Scrolls",
+        "f9a76537647989f9a0b6d001e320dac591c359e9e61a31f4ce11c88f207f0ad4" => "Vanilla",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// The model used for a skin.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SkinModel {
+    /// The classic model with 4px wide arms, used by Steve.
+    Classic,
+
+    /// The slim model with 3px wide arms, used by Alex.
+    Slim,
+}
+
+impl fmt::Display for SkinModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkinModel::Classic => f.write_str("steve"),
+            SkinModel::Slim => f.write_str("alex"),
+        }
+    }
+}
+
 /// Metadata relating to the skin, such as the model used for the skin
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct SkinMetadata {
-    /// The model used for the skin.
+    /// The model used for the skin, see [SkinMetadata::model_kind].
     pub model: String,
 }
 
+impl SkinMetadata {
+    /// Returns the model used for the skin, keeping models this library does not know about.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::profile::{SkinMetadata, SkinModelKind};
+    /// let metadata = SkinMetadata { model: String::from("slim") };
+    /// assert_eq!(metadata.model_kind(), SkinModelKind::Slim);
+    ///
+    /// let metadata = SkinMetadata { model: String::from("tall") };
+    /// assert_eq!(metadata.model_kind(), SkinModelKind::Unknown(String::from("tall")));
+    /// ```
+    pub fn model_kind(&self) -> SkinModelKind {
+        match self.model.as_str() {
+            "slim" => SkinModelKind::Slim,
+            "" | "classic" | "default" => SkinModelKind::Classic,
+            other => SkinModelKind::Unknown(other.to_string()),
+        }
+    }
+}
+
+/// The model named in [SkinMetadata], unlike [SkinModel] this keeps models this library does not know about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SkinModelKind {
+    /// The classic model with 4px wide arms, used by Steve.
+    Classic,
+
+    /// The slim model with 3px wide arms, used by Alex.
+    Slim,
+
+    /// A model this library does not know about.
+    Unknown(String),
+}
+
 /// A username change entry
 ///
 /// if `changed_to_at` is [None] then it is the original name as the name was never changed to from a previous one.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+///
+/// Entries are ordered by `changed_to_at`, with [None] ordered as `0` so the original name sorts first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UsernameEntry {
     /// The username
     pub name: String,
@@ -151,23 +1022,511 @@ pub struct UsernameEntry {
     pub changed_to_at: Option<u64>,
 }
 
+impl UsernameEntry {
+    fn sort_key(&self) -> (u64, bool, &str) {
+        (
+            self.changed_to_at.unwrap_or(0),
+            self.changed_to_at.is_some(),
+            &self.name,
+        )
+    }
+}
+
+impl Ord for UsernameEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for UsernameEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A user's username history, sorted chronologically (oldest first).
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::profile::{UsernameEntry, UsernameHistory};
+/// let history = UsernameHistory::new(vec![
+///     UsernameEntry { name: String::from("bree"), changed_to_at: Some(1640326151859) },
+///     UsernameEntry { name: String::from("brecert"), changed_to_at: None },
+/// ]);
+///
+/// assert_eq!(history.original_name(), Some("brecert"));
+/// assert_eq!(history.current_name(), Some("bree"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(from = "Vec<UsernameEntry>")]
+pub struct UsernameHistory(Vec<UsernameEntry>);
+
+impl UsernameHistory {
+    /// Creates a username history, sorting the entries.
+    ///
+    /// Entries without a `changed_to_at` are treated as being changed to at `0`.
+    pub fn new(mut entries: Vec<UsernameEntry>) -> Self {
+        entries.sort();
+        UsernameHistory(entries)
+    }
+
+    /// Returns the name the user originally had.
+    pub fn original_name(&self) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|entry| entry.changed_to_at.is_none())
+            .or_else(|| self.0.first())
+            .map(|entry| entry.name.as_str())
+    }
+
+    /// Returns the name the user currently has.
+    pub fn current_name(&self) -> Option<&str> {
+        self.0.last().map(|entry| entry.name.as_str())
+    }
+
+    /// Returns how long ago the user changed to their current name, or [None] if they never changed their name.
+    ///
+    /// This is measured against the system clock, and is zero if the change is in the future.
+    pub fn age_since_name_change(&self) -> Option<Duration> {
+        let changed_at = Duration::from_millis(self.0.last()?.changed_to_at?);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Some(now.saturating_sub(changed_at))
+    }
+
+    /// Returns the sorted entries.
+    pub fn into_inner(self) -> Vec<UsernameEntry> {
+        self.0
+    }
+}
+
+impl Deref for UsernameHistory {
+    type Target = [UsernameEntry];
+
+    fn deref(&self) -> &[UsernameEntry] {
+        &self.0
+    }
+}
+
+impl From<Vec<UsernameEntry>> for UsernameHistory {
+    fn from(entries: Vec<UsernameEntry>) -> Self {
+        UsernameHistory::new(entries)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl UsernameEntry {
+    /// Returns when the username was changed to the current entry, or [None] if it is the original name.
+    pub fn changed_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone;
+
+        let millis = i64::try_from(self.changed_to_at?).ok()?;
+        chrono::Utc.timestamp_millis_opt(millis).single()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::mojang_api::client::MockClient;
+    use crate::mojang_api::error::UuidError;
+
+    const PROFILE_JSON: &str = r#"{
+        "id" : "7a8084cd1f444a159bb1eef8d5b535a1",
+        "name" : "brecert",
+        "properties" : [ {
+          "name" : "textures",
+          "value" : "ewogICJ0aW1lc3RhbXAiIDogMTY0MDMyNjE1MTg1OSwKICAicHJvZmlsZUlkIiA6ICI3YTgwODRjZDFmNDQ0YTE1OWJiMWVlZjhkNWI1MzVhMSIsCiAgInByb2ZpbGVOYW1lIiA6ICJicmVjZXJ0IiwKICAidGV4dHVyZXMiIDogewogICAgIlNLSU4iIDogewogICAgICAidXJsIiA6ICJodHRwOi8vdGV4dHVyZXMubWluZWNyYWZ0Lm5ldC90ZXh0dXJlL2I4MTMwMjgyYjgwY2MwODg3MmJmYzg1ODk3NTM1MGFiM2YzZmNkNGIxZDE4NzE3YmZiNWI3YjgzOGZjZTRlYWEiLAogICAgICAibWV0YWRhdGEiIDogewogICAgICAgICJtb2RlbCIgOiAic2xpbSIKICAgICAgfQogICAgfQogIH0KfQ=="
+        } ]
+      }"#;
 
     #[test]
     fn test_parse() {
-        let json = r#"{
-            "id" : "7a8084cd1f444a159bb1eef8d5b535a1",
-            "name" : "brecert",
-            "properties" : [ {
-              "name" : "textures",
-              "value" : "ewogICJ0aW1lc3RhbXAiIDogMTY0MDMyNjE1MTg1OSwKICAicHJvZmlsZUlkIiA6ICI3YTgwODRjZDFmNDQ0YTE1OWJiMWVlZjhkNWI1MzVhMSIsCiAgInByb2ZpbGVOYW1lIiA6ICJicmVjZXJ0IiwKICAidGV4dHVyZXMiIDogewogICAgIlNLSU4iIDogewogICAgICAidXJsIiA6ICJodHRwOi8vdGV4dHVyZXMubWluZWNyYWZ0Lm5ldC90ZXh0dXJlL2I4MTMwMjgyYjgwY2MwODg3MmJmYzg1ODk3NTM1MGFiM2YzZmNkNGIxZDE4NzE3YmZiNWI3YjgzOGZjZTRlYWEiLAogICAgICAibWV0YWRhdGEiIDogewogICAgICAgICJtb2RlbCIgOiAic2xpbSIKICAgICAgfQogICAgfQogIH0KfQ=="
-            } ]
-          }"#;
-
-        let profile = serde_json::from_str::<Profile>(&json).unwrap();
-
-        assert_eq!(profile.textures().skin.url, "http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa")
+        let profile = serde_json::from_str::<Profile>(PROFILE_JSON).unwrap();
+
+        assert_eq!(profile.textures().unwrap().skin.url, "http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa");
+        assert_eq!(profile.skin_model(), SkinModel::Slim);
+        assert_eq!(profile.skin_url(), Some("http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"));
+        assert_eq!(profile.cape_url(), None);
+        assert!(!profile.has_cape());
+        assert_eq!(
+            profile.to_string(),
+            "brecert (7a8084cd1f444a159bb1eef8d5b535a1) [slim]"
+        );
+    }
+
+    #[test]
+    fn textures_entry_round_trip() {
+        let profile = serde_json::from_str::<Profile>(PROFILE_JSON).unwrap();
+        let entry = profile
+            .find_property("textures")
+            .unwrap()
+            .value
+            .as_ref()
+            .unwrap();
+
+        let decoded = decode_textures_entry(&entry.to_base64()).unwrap();
+        assert_eq!(&decoded, entry);
+    }
+
+    #[test]
+    fn test_multiple_properties() {
+        let mut raw = serde_json::from_str::<serde_json::Value>(PROFILE_JSON).unwrap();
+        let properties = raw["properties"].as_array_mut().unwrap();
+        properties.insert(
+            0,
+            serde_json::json!({ "name": "twitch", "value": "not base64 textures" }),
+        );
+
+        let profile = serde_json::from_value::<Profile>(raw).unwrap();
+
+        assert_eq!(profile.properties.len(), 2);
+        assert_eq!(profile.find_property("twitch").unwrap().value, None);
+        assert!(!profile.properties[0].is_textures());
+        assert!(profile.properties[1].is_textures());
+        assert_eq!(profile.find_textures_property(), profile.properties.get(1));
+        assert!(profile.find_property("missing").is_none());
+        assert_eq!(profile.skin_model(), SkinModel::Slim);
+    }
+
+    #[test]
+    fn test_no_properties() {
+        let profile = Profile {
+            id: String::from("7a8084cd1f444a159bb1eef8d5b535a1"),
+            name: String::from("brecert"),
+            properties: Vec::new(),
+            legacy: false,
+            demo: false,
+        };
+
+        assert!(profile.find_textures_property().is_none());
+        assert!(profile.textures().is_none());
+        assert_eq!(profile.skin_url(), None);
+        assert_eq!(profile.skin_model(), SkinModel::Classic);
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let mut profile = Profile::from_json(PROFILE_JSON).unwrap();
+
+        // The fixture was served in 2021.
+        assert!(!profile.is_fresh(Duration::from_secs(60 * 60)));
+        assert!(profile.is_fresh(Duration::MAX));
+
+        profile.properties.clear();
+        assert!(!profile.is_fresh(Duration::MAX));
+    }
+
+    #[test]
+    fn test_reload() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://sessionserver.mojang.com/session/minecraft/profile/7a8084cd1f444a159bb1eef8d5b535a1",
+                200,
+                PROFILE_JSON.replace("\"brecert\"", "\"bree\""),
+            )
+            .install();
+        let mut profile = Profile::from_json(PROFILE_JSON).unwrap();
+
+        profile.reload().unwrap();
+        assert_eq!(profile.name, "bree");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let profile = Profile::from_json(PROFILE_JSON).unwrap();
+
+        assert_eq!(Profile::from_json(&profile.to_json()).unwrap(), profile);
+        assert_eq!(
+            Profile::from_json(&profile.to_json_pretty()).unwrap(),
+            profile
+        );
+    }
+
+    #[test]
+    fn test_textures_raw() {
+        let profile = serde_json::from_str::<Profile>(PROFILE_JSON).unwrap();
+        let raw = serde_json::from_str::<serde_json::Value>(PROFILE_JSON).unwrap();
+
+        assert_eq!(
+            profile.textures_raw().unwrap(),
+            raw["properties"][0]["value"]
+        );
+
+        let json = serde_json::to_string(&profile).unwrap();
+        let reparsed = serde_json::from_str::<Profile>(&json).unwrap();
+        assert_eq!(reparsed, profile);
+        assert_eq!(reparsed.textures_raw(), profile.textures_raw());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_timestamp_datetime() {
+        let profile = serde_json::from_str::<Profile>(PROFILE_JSON).unwrap();
+
+        assert_eq!(
+            profile.properties[0]
+                .value
+                .as_ref()
+                .unwrap()
+                .timestamp_datetime()
+                .unwrap()
+                .to_rfc3339(),
+            "2021-12-24T06:09:11.859+00:00"
+        );
+    }
+
+    #[test]
+    fn test_legacy_and_demo() {
+        let profile = Profile::from_json(PROFILE_JSON).unwrap();
+        assert!(!profile.is_legacy());
+        assert!(!profile.is_demo());
+
+        let mut raw = serde_json::from_str::<serde_json::Value>(PROFILE_JSON).unwrap();
+        raw["legacy"] = serde_json::Value::Bool(true);
+        let profile = serde_json::from_value::<Profile>(raw).unwrap();
+        assert!(profile.is_legacy());
+        assert!(!profile.is_demo());
+    }
+
+    #[test]
+    fn test_eq_by_uuid() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let profile = Profile::from_json(PROFILE_JSON).unwrap();
+        let mut refetched = profile.clone();
+        refetched.properties.clear();
+
+        assert_ne!(profile, refetched);
+        assert!(profile.eq_by_uuid(&refetched));
+
+        let hash = |profile: &Profile| {
+            let mut hasher = DefaultHasher::new();
+            profile.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&profile), hash(&refetched));
+    }
+
+    #[test]
+    fn test_fetch_invalid_uuid() {
+        assert!(matches!(
+            Profile::fetch("not-a-uuid"),
+            Err(ApiError::InvalidUuid(UuidError::InvalidLength(10)))
+        ));
+    }
+
+    #[test]
+    fn test_fetch_batch_keeps_errors() {
+        let results = Profile::fetch_batch_with_delay(&["not-a-uuid", "also-not"], Duration::ZERO);
+
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|res| matches!(res, Err(ApiError::InvalidUuid(_)))));
+    }
+
+    #[test]
+    fn test_profile_cache() {
+        let profile = Profile::from_json(PROFILE_JSON).unwrap();
+        let with_id = |id: &str| Profile {
+            id: id.into(),
+            ..profile.clone()
+        };
+
+        let mut cache = ProfileCache::with_capacity(Duration::from_secs(60), 2);
+        cache.insert(with_id("a"));
+        cache.insert(with_id("b"));
+        assert!(cache.get("a").is_some());
+
+        // "b" is now the least recently used.
+        cache.insert(with_id("c"));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+
+        assert_eq!(cache.remove("a"), Some(with_id("a")));
+        assert_eq!(cache.len(), 1);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_profile_cache_normalizes_keys() {
+        let mut cache = ProfileCache::new(Duration::from_secs(60));
+        cache.insert(Profile::from_json(PROFILE_JSON).unwrap());
+
+        assert!(cache.get("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1").is_some());
+        assert!(cache.get("7A8084CD1F444A159BB1EEF8D5B535A1").is_some());
+        assert!(cache
+            .get_or_fetch("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1")
+            .is_ok());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_profile_cache_small_capacity() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://sessionserver.mojang.com/session/minecraft/profile/7a8084cd1f444a159bb1eef8d5b535a1",
+                200,
+                PROFILE_JSON,
+            )
+            .respond(
+                "https://sessionserver.mojang.com/session/minecraft/profile/069a79f444e94726a5befca90e38aaf5",
+                200,
+                r#"{ "id": "069a79f444e94726a5befca90e38aaf5", "name": "Notch", "properties": [] }"#,
+            )
+            .install();
+
+        for capacity in [0, 1] {
+            let mut cache = ProfileCache::with_capacity(Duration::from_secs(60), capacity);
+
+            let profile = cache
+                .get_or_fetch("7a8084cd1f444a159bb1eef8d5b535a1")
+                .unwrap();
+            assert_eq!(profile.name, "brecert");
+            let profile = cache
+                .get_or_fetch("069a79f444e94726a5befca90e38aaf5")
+                .unwrap();
+            assert_eq!(profile.name, "Notch");
+
+            assert_eq!(cache.len(), 1);
+            assert!(cache.get("7a8084cd1f444a159bb1eef8d5b535a1").is_none());
+        }
+    }
+
+    #[test]
+    fn test_profile_cache_expiry() {
+        let mut cache = ProfileCache::new(Duration::ZERO);
+        cache.insert(Profile::from_json(PROFILE_JSON).unwrap());
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("7a8084cd1f444a159bb1eef8d5b535a1").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_username_history() {
+        let entry = |name: &str, changed_to_at| UsernameEntry {
+            name: name.into(),
+            changed_to_at,
+        };
+        let history = UsernameHistory::new(vec![
+            entry("third", Some(300)),
+            entry("first", None),
+            entry("second", Some(200)),
+        ]);
+
+        assert_eq!(history.original_name(), Some("first"));
+        assert_eq!(history.current_name(), Some("third"));
+        assert_eq!(
+            history.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            ["first", "second", "third"]
+        );
+
+        let empty = UsernameHistory::default();
+        assert_eq!(empty.original_name(), None);
+        assert_eq!(empty.current_name(), None);
+        assert_eq!(empty.age_since_name_change(), None);
+    }
+
+    #[test]
+    fn test_age_since_name_change() {
+        let entry = |name: &str, changed_to_at| UsernameEntry {
+            name: name.into(),
+            changed_to_at,
+        };
+
+        let never_changed = UsernameHistory::new(vec![entry("first", None)]);
+        assert_eq!(never_changed.age_since_name_change(), None);
+
+        // 2021-12-24, so at least a year ago whenever the test runs.
+        let changed = UsernameHistory::new(vec![
+            entry("first", None),
+            entry("second", Some(1640326151859)),
+        ]);
+        let age = changed.age_since_name_change().unwrap();
+        assert!(age > Duration::from_secs(365 * 24 * 60 * 60));
+
+        let future = UsernameHistory::new(vec![entry("second", Some(u64::MAX))]);
+        assert_eq!(future.age_since_name_change(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_profile_age_since_name_change() {
+        let profile = Profile::from_json(PROFILE_JSON).unwrap();
+        let url = "https://api.mojang.com/user/profiles/7a8084cd1f444a159bb1eef8d5b535a1/names";
+
+        {
+            let _guard = MockClient::new()
+                .respond(
+                    url,
+                    200,
+                    r#"[{ "name": "first" }, { "name": "brecert", "changedToAt": 1640326151859 }]"#,
+                )
+                .install();
+            assert!(profile.age_since_name_change().is_some());
+        }
+
+        // The retired endpoint now responds with not found.
+        let _guard = MockClient::new().install();
+        assert_eq!(profile.age_since_name_change(), None);
+    }
+
+    #[test]
+    fn test_username_entry_ord() {
+        let entry = |name: &str, changed_to_at| UsernameEntry {
+            name: name.into(),
+            changed_to_at,
+        };
+        let mut history = [
+            entry("second", Some(1423047705000)),
+            entry("fourth", Some(1640326151859)),
+            entry("first", None),
+            entry("third", Some(1500000000000)),
+        ];
+        history.sort();
+
+        assert_eq!(
+            history.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            ["first", "second", "third", "fourth"]
+        );
+        assert!(entry("a", None) < entry("a", Some(0)));
+    }
+
+    #[test]
+    fn test_skin_model() {
+        assert_eq!(SkinModel::Classic.to_string(), "steve");
+        assert_eq!(SkinModel::Slim.to_string(), "alex");
+        assert_eq!(
+            serde_json::to_string(&SkinModel::Slim).unwrap(),
+            r#""slim""#
+        );
+        assert_eq!(
+            serde_json::from_str::<SkinModel>(r#""classic""#).unwrap(),
+            SkinModel::Classic
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_changed_at_datetime() {
+        let original = UsernameEntry {
+            name: "brecert".into(),
+            changed_to_at: None,
+        };
+        let changed = UsernameEntry {
+            name: "bree".into(),
+            changed_to_at: Some(1640326151859),
+        };
+
+        assert_eq!(original.changed_at_datetime(), None);
+        assert_eq!(
+            changed.changed_at_datetime().unwrap().to_rfc3339(),
+            "2021-12-24T06:09:11.859+00:00"
+        );
     }
 }
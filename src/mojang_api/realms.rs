@@ -0,0 +1,134 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::mojang_api::client::{get_with_config, parse_json, post_with_config, ClientConfig};
+use crate::mojang_api::error::ApiError;
+use crate::mojang_api::user::User;
+
+const REALMS: &str = "https://pc.realms.minecraft.net";
+
+/// The game version sent to Realms, which rejects clients it considers outdated.
+const CLIENT_VERSION: &str = "1.20.4";
+
+/// Realms authenticates with a cookie built from the session, rather than a bearer token.
+fn session_config(access_token: &str, profile: &User) -> ClientConfig {
+    let mut config = ClientConfig::get_default();
+    config.extra_headers.push((
+        String::from("Cookie"),
+        format!(
+            "sid=token:{}:{};user={};version={}",
+            access_token, profile.id, profile.name, CLIENT_VERSION
+        ),
+    ));
+    config
+}
+
+fn deserialize_nullable_string<'de, D>(de: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(de)?.unwrap_or_default())
+}
+
+/// The state of a [Realm].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RealmState {
+    /// The realm can be joined.
+    Open,
+
+    /// The realm has been closed by its owner.
+    Closed,
+
+    /// The realm has been purchased, but no world has been set up yet.
+    Uninitialized,
+
+    /// The realm has been locked by Mojang.
+    AdminLock,
+
+    /// A state this library does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A realm the user owns or has been invited to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Realm {
+    /// The id of the realm.
+    pub id: u64,
+
+    /// The name of the realm, empty if it has not been set.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub name: String,
+
+    /// The username of the owner of the realm.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub owner: String,
+
+    /// The message of the day of the realm, empty if it has not been set.
+    #[serde(default, deserialize_with = "deserialize_nullable_string")]
+    pub motd: String,
+
+    /// The state of the realm.
+    pub state: RealmState,
+}
+
+#[derive(Deserialize)]
+struct Worlds {
+    servers: Vec<Realm>,
+}
+
+#[derive(Serialize)]
+struct InviteRequest<'a> {
+    name: &'a str,
+    uuid: &'a str,
+}
+
+/// Lists the realms the user owns or has been invited to.
+///
+/// `profile` is the user the access token belongs to, such as the selected profile from [yggdrasil](crate::mojang_api::yggdrasil).
+pub fn list_worlds(access_token: &str, profile: &User) -> Result<Vec<Realm>, ApiError> {
+    let url = format!("{}/worlds", REALMS);
+    let res = get_with_config(url, &session_config(access_token, profile))?;
+    let worlds: Worlds = parse_json(&res)?;
+    Ok(worlds.servers)
+}
+
+/// Invites a player to a realm owned by the user, returning the updated realm.
+///
+/// `profile` is the user the access token belongs to, and `player` is the user being invited.
+pub fn invite(
+    access_token: &str,
+    profile: &User,
+    realm_id: u64,
+    player: &User,
+) -> Result<Realm, ApiError> {
+    let url = format!("{}/invites/{}", REALMS, realm_id);
+    let body = InviteRequest {
+        name: &player.name,
+        uuid: &player.id,
+    };
+    let res = post_with_config(url, &body, &session_config(access_token, profile))?;
+    parse_json(&res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_worlds() {
+        let json = r#"{
+            "servers": [
+                { "id": 1, "name": "My Realm", "owner": "brecert", "motd": "hello", "state": "OPEN" },
+                { "id": 2, "name": null, "owner": "brecert", "motd": null, "state": "UNINITIALIZED" },
+                { "id": 3, "name": "Locked", "owner": "brecert", "motd": "", "state": "SOMETHING_NEW" }
+            ]
+        }"#;
+        let worlds: Worlds = serde_json::from_str(json).unwrap();
+
+        assert_eq!(worlds.servers[0].state, RealmState::Open);
+        assert_eq!(worlds.servers[1].name, "");
+        assert_eq!(worlds.servers[1].state, RealmState::Uninitialized);
+        assert_eq!(worlds.servers[2].state, RealmState::Unknown);
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::mojang_api::client::{
+    get_empty_with_config, get_with_config, parse_json, post_empty_with_config, ClientConfig,
+};
+use crate::mojang_api::error::ApiError;
+
+const SECURITY: &str = "https://api.mojang.com/user/security";
+
+fn bearer_config(access_token: &str) -> ClientConfig {
+    let mut config = ClientConfig::get_default();
+    config.extra_headers.push((
+        String::from("Authorization"),
+        format!("Bearer {}", access_token),
+    ));
+    config
+}
+
+/// A security question the user has to answer to trust their current location.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "RawChallenge")]
+pub struct SecurityChallenge {
+    /// The id to answer the question with, see [SecurityAnswer::id].
+    pub answer_id: u64,
+
+    /// The id of the question.
+    pub question_id: u64,
+
+    /// The question, such as `"What is your favorite pet's name?"`.
+    pub question: String,
+}
+
+#[derive(Deserialize)]
+struct RawChallenge {
+    answer: RawId,
+    question: RawQuestion,
+}
+
+#[derive(Deserialize)]
+struct RawId {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct RawQuestion {
+    id: u64,
+    question: String,
+}
+
+impl From<RawChallenge> for SecurityChallenge {
+    fn from(raw: RawChallenge) -> Self {
+        SecurityChallenge {
+            answer_id: raw.answer.id,
+            question_id: raw.question.id,
+            question: raw.question.question,
+        }
+    }
+}
+
+/// An answer to a [SecurityChallenge].
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SecurityAnswer {
+    /// The [SecurityChallenge::answer_id] of the question being answered.
+    pub id: u64,
+
+    /// The answer to the question.
+    pub answer: String,
+}
+
+/// Checks if the user's current location is trusted, if it isn't the security questions need to be answered.
+pub fn is_secured(access_token: &str) -> Result<bool, ApiError> {
+    let url = format!("{}/location", SECURITY);
+    match get_empty_with_config(url, &bearer_config(access_token)) {
+        Ok(()) => Ok(true),
+        Err(err) if err.status_code() == Some(403) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Gets the security questions the user has to answer, this is empty if the user has no security questions.
+pub fn get_challenges(access_token: &str) -> Result<Vec<SecurityChallenge>, ApiError> {
+    let url = format!("{}/challenges", SECURITY);
+    parse_json(&get_with_config(url, &bearer_config(access_token))?)
+}
+
+/// Answers the security questions, trusting the user's current location if the answers are correct.
+///
+/// Incorrect answers are returned as an [ApiError::Request] with the status `403`.
+pub fn submit_answers(access_token: &str, answers: &[SecurityAnswer]) -> Result<(), ApiError> {
+    let url = format!("{}/location", SECURITY);
+    post_empty_with_config(url, &answers, &bearer_config(access_token))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_challenges() {
+        let json = r#"[
+            { "answer": { "id": 123 }, "question": { "id": 1, "question": "What is your favorite pet's name?" } },
+            { "answer": { "id": 456 }, "question": { "id": 2, "question": "What is your favorite movie?" } }
+        ]"#;
+        let challenges: Vec<SecurityChallenge> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].answer_id, 123);
+        assert_eq!(challenges[1].question, "What is your favorite movie?");
+    }
+}
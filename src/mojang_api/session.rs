@@ -0,0 +1,143 @@
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+use crate::mojang_api::client::{get, parse_json, percent_encode, post_empty};
+use crate::mojang_api::error::ApiError;
+use crate::mojang_api::profile::Profile;
+use crate::mojang_api::user::validate_username;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JoinRequest<'a> {
+    access_token: &'a str,
+    selected_profile: &'a str,
+    server_id: &'a str,
+}
+
+/// Tells the session server that the client is joining a server, this is done by the client.
+///
+/// `selected_profile` is the UUID of the player without dashes,
+/// and `server_id` is the server hash computed from the server id, shared secret, and server public key with [server_id_hash].
+pub fn join(access_token: &str, selected_profile: &str, server_id: &str) -> Result<(), ApiError> {
+    let url = "https://sessionserver.mojang.com/session/minecraft/join";
+    post_empty(
+        url,
+        &JoinRequest {
+            access_token,
+            selected_profile,
+            server_id,
+        },
+    )
+}
+
+/// Checks if the player has joined the server with [join], this is done by the server.
+///
+/// If `ip` is provided then the session server also checks that the player joined from that ip.
+///
+/// If the player has not joined, Mojang responds with no content,
+/// which is returned as an [ApiError::Request] with the status `204`.
+///
+/// The username is checked with [validate_username] first, an invalid username is returned as [ApiError::InvalidUsername] without making a request.
+pub fn has_joined(username: &str, server_id: &str, ip: Option<&str>) -> Result<Profile, ApiError> {
+    validate_username(username)?;
+    let mut url = format!(
+        "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={}&serverId={}",
+        username,
+        percent_encode(server_id)
+    );
+    if let Some(ip) = ip {
+        url.push_str("&ip=");
+        url.push_str(&percent_encode(ip));
+    }
+    parse_json(&get(url)?)
+}
+
+/// Formats a SHA1 digest of the bytes the way the game does, as a signed hex number.
+///
+/// The game formats the digest with Java's `new BigInteger(digest).toString(16)`,
+/// so digests with the highest bit set are negative, and leading zeros are dropped.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::session::notch_digest;
+/// assert_eq!(notch_digest(b"Notch"), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+/// assert_eq!(notch_digest(b"jeb_"), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+/// assert_eq!(notch_digest(b"simon"), "88e16a1019277b15d58faf0541e11910eb756f6");
+/// ```
+pub fn notch_digest(bytes: &[u8]) -> String {
+    format_signed_digest(Sha1::digest(bytes).into())
+}
+
+/// Computes the server hash sent with [join] and [has_joined].
+///
+/// `server_id` is the id sent by the server in its encryption request, which is empty for vanilla servers,
+/// `shared_secret` is the secret generated by the client, and `server_public_key` is the server's DER encoded public key.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::session::{notch_digest, server_id_hash};
+/// assert_eq!(server_id_hash("", b"secret", b"key"), notch_digest(b"secretkey"));
+/// ```
+pub fn server_id_hash(server_id: &str, shared_secret: &[u8], server_public_key: &[u8]) -> String {
+    let digest = Sha1::new()
+        .chain_update(server_id.as_bytes())
+        .chain_update(shared_secret)
+        .chain_update(server_public_key)
+        .finalize();
+    format_signed_digest(digest.into())
+}
+
+/// Formats a digest as a two's complement signed big endian number in hex, without leading zeros.
+fn format_signed_digest(mut digest: [u8; 20]) -> String {
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        // Negate the digest by inverting it and adding one.
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            let (inverted, overflow) = (!*byte).overflowing_add(carry as u8);
+            *byte = inverted;
+            carry = overflow;
+        }
+    }
+
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let hex = hex.trim_start_matches('0');
+    let hex = if hex.is_empty() { "0" } else { hex };
+
+    if negative {
+        format!("-{}", hex)
+    } else {
+        hex.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mojang_api::client::MockClient;
+    use crate::mojang_api::error::UsernameError;
+
+    #[test]
+    fn has_joined_encodes_query() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://sessionserver.mojang.com/session/minecraft/hasJoined?username=brecert&serverId=-1a%262&ip=%3A%3A1%23",
+                200,
+                r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert", "properties": [] }"#,
+            )
+            .install();
+
+        let profile = has_joined("brecert", "-1a&2", Some("::1#")).unwrap();
+        assert_eq!(profile.name, "brecert");
+    }
+
+    #[test]
+    fn has_joined_validates_username() {
+        assert!(matches!(
+            has_joined("bre&cert", "0", None),
+            Err(ApiError::InvalidUsername(UsernameError::InvalidCharacter(
+                '&'
+            )))
+        ));
+    }
+}
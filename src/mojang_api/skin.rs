@@ -0,0 +1,275 @@
+use crate::mojang_api::client::get_raw;
+use crate::mojang_api::error::ApiError;
+use crate::mojang_api::profile::{texture_id_from_url, SkinModel};
+use crate::mojang_api::uuid::strip_uuid_dashes;
+
+/// Returns the default skin model the game uses for a player without a custom skin.
+///
+/// The uuid can be either with or without dashes, [None] is returned if it is not a valid uuid.
+///
+/// This is the same as Java's `(uuid.hashCode() & 1) == 1` being slim, which is how the game picked between
+/// Steve and Alex before the additional default skins were added in 1.19.3.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::skin::default_skin;
+/// # use minecraft_utils::mojang_api::SkinModel;
+/// // Notch
+/// assert_eq!(default_skin("069a79f444e94726a5befca90e38aaf5"), Some(SkinModel::Classic));
+/// // jeb_
+/// assert_eq!(default_skin("853c80ef-3c37-49fd-aa49-938b674adae6"), Some(SkinModel::Slim));
+/// assert_eq!(default_skin("not a uuid"), None);
+/// ```
+pub fn default_skin(uuid: &str) -> Option<SkinModel> {
+    let raw = strip_uuid_dashes(uuid).ok()?;
+    let bits = u128::from_str_radix(&raw, 16).ok()?;
+    // Java's UUID hashCode xors together each 32 bit section of the uuid.
+    let hash = (0..4).fold(0u32, |hash, i| hash ^ (bits >> (i * 32)) as u32);
+
+    if hash & 1 == 1 {
+        Some(SkinModel::Slim)
+    } else {
+        Some(SkinModel::Classic)
+    }
+}
+
+/// Texture ids of the default skins, in both the classic and slim models.
+///
+/// This includes the original Steve and Alex textures, and the nine default skins added in 1.19.3.
+/// When Mojang adds or changes a default skin, its id can be found by setting an account to that skin
+/// and reading the id from the profile's skin url with [SkinData::texture_id](crate::mojang_api::profile::SkinData::texture_id).
+pub const DEFAULT_SKIN_TEXTURE_IDS: [&str; 20] = [
+    // Steve and Alex before 1.19.3
+    "1a4af718455d4aab528e7a61f86fa25e6a369d1768dcb13f7df319a713eb810b",
+    "3b60a1f6d562f52aaebbf1434f1de147933a3affe0e764fa49ea057536623cd3",
+    // Steve
+    "31f477eb1a7beee631c2ca64d06f8f68fa93a3386d04452ab27f43acdf1b60cb",
+    "d5c4ee5ce20aed9e33e866c66caa37178606234b3721084bf01d13320fb2eb3f",
+    // Alex
+    "1abc803022d8300ab7578b189294cce39622d9a404cdc00d3feacfdf45be6981",
+    "46acd06e8483b176e8ea39fc12fe105eb3a2a4970f5100057e9d84d4b60bdfa7",
+    // Ari
+    "4c05ab9e07b3505dc3ec11370c3bdce5570ad2fb2b562e9b9dd9cf271f81aa44",
+    "6ac6ca262d67bcfb3dbc924ba8215a18195497c780058a5749de674217721892",
+    // Efe
+    "daf3d88ccb38f11f74814e92053d92f7728ddb1a7955652a60e30cb27ae6659f",
+    "fece7017b1bb13926d1158864b283b8b930271f80a90482f174cca6a17e88236",
+    // Kai
+    "e5cdc3243b2153ab28a159861be643a4fc1e3c17d291cdd3e57a7f370ad676f3",
+    "226c617fde5b1ba569aa08bd2cb6fd84c93337532a872b3eb7bf66bdd5b395f8",
+    // Makena
+    "dc0fcfaf2aa040a83dc0de4e56058d1bbb2ea40157501f3e7d15dc245e493095",
+    "7cb3ba52ddd5cc82c0b050c3f920f87da36add80165846f479079663805433db",
+    // Noor
+    "90e75cd429ba6331cd210b9bd19399527ee3bab467b5a9f61cb8a27b177f6789",
+    "6c160fbd16adbc4bff2409e70180d911002aebcfa811eb6ec3d1040761aea6dd",
+    // Sunny
+    "a3bd16079f764cd541e072e888fe43885e711f98658323db0f9a6045da91ee7a",
+    "b66bc80f002b10371e2fa23de6f230dd5e2f3affc2e15786f65bc9be4c6eb71a",
+    // Zuri
+    "f5dddb41dcafef616e959c2817808e0be741c89ffbfed39134a13e75b811863d",
+    "eee522611005acf256dbd152e992c60c0bb7978cb0f3127807700e478ad97664",
+];
+
+/// Returns if the skin url is one of the default skins, see [DEFAULT_SKIN_TEXTURE_IDS].
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::skin::is_default_skin_url;
+/// assert!(is_default_skin_url("http://textures.minecraft.net/texture/31f477eb1a7beee631c2ca64d06f8f68fa93a3386d04452ab27f43acdf1b60cb"));
+/// assert!(!is_default_skin_url("http://textures.minecraft.net/texture/b8130282b80cc08872bfc858975350ab3f3fcd4b1d18717bfb5b7b838fce4eaa"));
+/// ```
+pub fn is_default_skin_url(url: &str) -> bool {
+    texture_id_from_url(url).is_some_and(|id| DEFAULT_SKIN_TEXTURE_IDS.contains(&id))
+}
+
+const CRAFATAR: &str = "https://crafatar.com";
+
+/// Returns the url of the user's skin texture on Crafatar.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::skin::crafatar_skin_url;
+/// assert_eq!(
+///     crafatar_skin_url("7a8084cd1f444a159bb1eef8d5b535a1"),
+///     "https://crafatar.com/skins/7a8084cd1f444a159bb1eef8d5b535a1"
+/// );
+/// ```
+pub fn crafatar_skin_url(uuid: &str) -> String {
+    format!("{}/skins/{}", CRAFATAR, uuid)
+}
+
+/// Returns the url of an image of the user's face on Crafatar.
+///
+/// `size` is the width and height of the image in pixels, and is clamped to the `1..=512` Crafatar allows.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::skin::crafatar_head_url;
+/// assert_eq!(
+///     crafatar_head_url("7a8084cd1f444a159bb1eef8d5b535a1", 64),
+///     "https://crafatar.com/avatars/7a8084cd1f444a159bb1eef8d5b535a1?size=64"
+/// );
+/// assert_eq!(
+///     crafatar_head_url("7a8084cd1f444a159bb1eef8d5b535a1", 1000),
+///     "https://crafatar.com/avatars/7a8084cd1f444a159bb1eef8d5b535a1?size=512"
+/// );
+/// ```
+pub fn crafatar_head_url(uuid: &str, size: u32) -> String {
+    format!("{}/avatars/{}?size={}", CRAFATAR, uuid, size.clamp(1, 512))
+}
+
+/// Returns the url of a 3d render of the user's body on Crafatar.
+///
+/// Crafatar sizes renders by `scale`, a multiplier of the skin's pixels, which is clamped to the `1..=10` Crafatar allows.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::skin::crafatar_body_url;
+/// assert_eq!(
+///     crafatar_body_url("7a8084cd1f444a159bb1eef8d5b535a1", 4),
+///     "https://crafatar.com/renders/body/7a8084cd1f444a159bb1eef8d5b535a1?scale=4"
+/// );
+/// ```
+pub fn crafatar_body_url(uuid: &str, scale: u32) -> String {
+    format!(
+        "{}/renders/body/{}?scale={}",
+        CRAFATAR,
+        uuid,
+        scale.clamp(1, 10)
+    )
+}
+
+/// The layout of a skin texture, which depends on the version of the game it was made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkinFormat {
+    /// Skins from before 1.8, which are half as tall as they are wide, usually `64×32`.
+    ///
+    /// These have no second layer for the body and limbs, and the left limbs mirror the right limbs.
+    Legacy,
+
+    /// Skins used since 1.8, which are `64×64` with separate left limbs and a second layer for each body part.
+    Modern,
+
+    /// Square skins larger than `64×64`, such as `128×128` or `256×256`.
+    ///
+    /// The vanilla game does not render these, but some accounts still have them and some clients support them.
+    Hd,
+
+    /// Dimensions which do not match any skin format.
+    Unknown,
+}
+
+/// The dimensions of a skin texture, and the [SkinFormat] they correspond to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SkinVariant {
+    /// The width of the texture in pixels.
+    pub width: u32,
+
+    /// The height of the texture in pixels.
+    pub height: u32,
+
+    /// The format of the texture.
+    pub format: SkinFormat,
+}
+
+impl SkinVariant {
+    /// Returns the variant for a texture with the given dimensions.
+    ///
+    /// | Dimensions                   | Format               |
+    /// |------------------------------|----------------------|
+    /// | `64×32` (or `128×64`, ...)   | [SkinFormat::Legacy] |
+    /// | `64×64`                      | [SkinFormat::Modern] |
+    /// | `128×128`, `256×256`, ...    | [SkinFormat::Hd]     |
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::skin::{SkinFormat, SkinVariant};
+    /// assert_eq!(SkinVariant::from_dimensions(64, 32).format, SkinFormat::Legacy);
+    /// assert_eq!(SkinVariant::from_dimensions(64, 64).format, SkinFormat::Modern);
+    /// assert_eq!(SkinVariant::from_dimensions(256, 256).format, SkinFormat::Hd);
+    /// assert_eq!(SkinVariant::from_dimensions(64, 48).format, SkinFormat::Unknown);
+    /// ```
+    pub fn from_dimensions(width: u32, height: u32) -> Self {
+        let format = if width == 0 || !width.is_multiple_of(64) {
+            SkinFormat::Unknown
+        } else if height.checked_mul(2) == Some(width) {
+            SkinFormat::Legacy
+        } else if height == 64 && width == 64 {
+            SkinFormat::Modern
+        } else if height == width {
+            SkinFormat::Hd
+        } else {
+            SkinFormat::Unknown
+        };
+
+        SkinVariant {
+            width,
+            height,
+            format,
+        }
+    }
+
+    /// Returns the variant of a png image, read from its header.
+    ///
+    /// [None] is returned if the bytes are not a png image.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::skin::{SkinFormat, SkinVariant};
+    /// let mut header = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    /// header.extend_from_slice(&64u32.to_be_bytes());
+    /// header.extend_from_slice(&32u32.to_be_bytes());
+    ///
+    /// assert_eq!(SkinVariant::from_png(&header).unwrap().format, SkinFormat::Legacy);
+    /// assert_eq!(SkinVariant::from_png(b"not a png"), None);
+    /// ```
+    pub fn from_png(bytes: &[u8]) -> Option<Self> {
+        // The IHDR chunk is always first, directly after the 8 byte signature and the chunk's length.
+        if !bytes.starts_with(PNG_SIGNATURE) || bytes.get(12..16)? != b"IHDR" {
+            return None;
+        }
+        let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+        Some(SkinVariant::from_dimensions(width, height))
+    }
+}
+
+/// The bytes every png image starts with.
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Downloads a texture, failing with [ApiError::InvalidImage] if it is not a png image.
+pub(crate) fn download_png(url: &str) -> Result<Vec<u8>, ApiError> {
+    let bytes = get_raw(url)?;
+    if bytes.starts_with(PNG_SIGNATURE) {
+        Ok(bytes)
+    } else {
+        Err(ApiError::InvalidImage)
+    }
+}
+
+/// The downloaded skin and cape textures of a user, as png images.
+///
+/// See [Profile::download_textures](crate::mojang_api::Profile::download_textures).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerTexture {
+    /// The skin texture, [None] if the profile has no textures.
+    pub skin: Option<Vec<u8>>,
+
+    /// The cape texture, [None] if the user does not have a cape.
+    pub cape: Option<Vec<u8>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_dimensions_huge_height() {
+        let variant = SkinVariant::from_dimensions(64, u32::MAX);
+        assert_eq!(variant.format, SkinFormat::Unknown);
+
+        let variant = SkinVariant::from_dimensions(u32::MAX - 63, u32::MAX / 2 + 1);
+        assert_eq!(variant.format, SkinFormat::Unknown);
+    }
+}
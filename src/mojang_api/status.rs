@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mojang_api::client::{get, parse_json};
+use crate::mojang_api::error::ApiError;
+
+/// The status of a Mojang service.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceStatus {
+    /// The service is working normally.
+    Green,
+
+    /// The service is having some issues.
+    Yellow,
+
+    /// The service is unavailable.
+    Red,
+
+    /// A status this library does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Fetches the status of each Mojang service, keyed by the service's domain.
+///
+/// This endpoint is not stable and Mojang has changed or disabled it in the past.
+pub fn fetch() -> Result<BTreeMap<String, ServiceStatus>, ApiError> {
+    let statuses: Vec<BTreeMap<String, ServiceStatus>> =
+        parse_json(&get("https://status.mojang.com/check")?)?;
+    Ok(statuses.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mojang_api::client::MockClient;
+
+    #[test]
+    fn parse_statuses() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://status.mojang.com/check",
+                200,
+                r#"[
+                    {"minecraft.net": "green"},
+                    {"session.minecraft.net": "yellow"},
+                    {"api.mojang.com": "red"},
+                    {"textures.minecraft.net": "grey"}
+                ]"#,
+            )
+            .install();
+
+        let statuses = fetch().unwrap();
+
+        assert_eq!(statuses["minecraft.net"], ServiceStatus::Green);
+        assert_eq!(statuses["session.minecraft.net"], ServiceStatus::Yellow);
+        assert_eq!(statuses["api.mojang.com"], ServiceStatus::Red);
+        assert_eq!(statuses["textures.minecraft.net"], ServiceStatus::Unknown);
+        assert_eq!(statuses.len(), 4);
+    }
+}
@@ -1,9 +1,35 @@
-use crate::mojang_api::client::{get, post};
-use crate::mojang_api::error::{ApiError, UsernameError};
+use crate::mojang_api::client::{get, parse_json, post};
+#[cfg(feature = "async")]
+use crate::mojang_api::client::{get_async, parse_json_async, post_async};
+use crate::mojang_api::error::{ApiError, UserParseError, UsernameError, UuidError};
+use crate::mojang_api::profile::{Profile, UsernameEntry};
+use crate::mojang_api::uuid::{format_uuid, strip_uuid_dashes, validate_uuid};
 
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
 
 /// Basic user information.
+///
+/// Displays and parses as `name (uuid)`.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::User;
+/// let user = User {
+///     id: String::from("7a8084cd1f444a159bb1eef8d5b535a1"),
+///     name: String::from("brecert"),
+/// };
+///
+/// assert_eq!(user.to_string(), "brecert (7a8084cd1f444a159bb1eef8d5b535a1)");
+/// assert_eq!(user.to_string().parse(), Ok(user));
+/// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct User {
     /// The UUID of the user.
@@ -12,28 +38,547 @@ pub struct User {
     pub name: String,
 }
 
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)
+    }
+}
+
+impl FromStr for User {
+    type Err = UserParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, id) = s
+            .rsplit_once(" (")
+            .and_then(|(name, rest)| Some((name, rest.strip_suffix(')')?)))
+            .ok_or(UserParseError::InvalidFormat)?;
+
+        if id.is_empty() {
+            return Err(UserParseError::InvalidFormat);
+        }
+
+        validate_username(name)?;
+
+        Ok(User {
+            id: id.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
 impl User {
-    fn fetch(username: &str) -> Result<Self, ApiError> {
+    /// Returns the user's name as a [Username], for comparing it case-insensitively.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::user::{User, Username};
+    /// let user: User = "BreCert (7a8084cd1f444a159bb1eef8d5b535a1)".parse().unwrap();
+    ///
+    /// assert_eq!(user.username().unwrap(), Username::new("brecert").unwrap());
+    /// ```
+    pub fn username(&self) -> Result<Username, UsernameError> {
+        Username::new(&self.name)
+    }
+
+    /// Fetches the user currently using the username.
+    ///
+    /// Accepts anything which can be borrowed as a string, such as a [Username].
+    pub fn fetch(username: impl AsRef<str>) -> Result<Self, ApiError> {
+        let url = format!(
+            "https://api.mojang.com/users/profiles/minecraft/{}",
+            username.as_ref()
+        );
+        parse_json(&get(url)?)
+    }
+
+    /// Fetches the user who was using the username at the given time.
+    ///
+    /// `timestamp` is a unix timestamp in seconds.
+    ///
+    /// Mojang only keeps limited history for this lookup, timestamps from before a name was first changed,
+    /// or before name changes were possible, may not resolve to the original owner.
+    /// Mojang has also been known to ignore the timestamp entirely, in which case the current owner is returned.
+    pub fn fetch_at_time(username: impl AsRef<str>, timestamp: u64) -> Result<Self, ApiError> {
+        let url = format!(
+            "https://api.mojang.com/users/profiles/minecraft/{}?at={}",
+            username.as_ref(),
+            timestamp
+        );
+        parse_json(&get(url)?)
+    }
+
+    /// Fetches the user with the UUID.
+    ///
+    /// This uses a lookup endpoint which only returns the id and name,
+    /// so it is lighter than fetching the full [Profile](crate::mojang_api::Profile) with its textures.
+    pub fn fetch_by_uuid(uuid: &str) -> Result<Self, ApiError> {
+        validate_uuid(uuid)?;
+        let url = format!(
+            "https://api.minecraftservices.com/minecraft/profile/lookup/{}",
+            uuid
+        );
+        parse_json(&get(url)?)
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_async(username: &str) -> Result<Self, ApiError> {
         let url = format!(
             "https://api.mojang.com/users/profiles/minecraft/{}",
             username
         );
-        Ok(get(url)?.json()?)
+        parse_json_async(get_async(url).await?).await
     }
 }
 
 /// Gets the UUID of the username
-pub fn get_username_uuid(username: &str) -> Result<String, ApiError> {
+///
+/// Accepts anything which can be borrowed as a string, such as a [Username].
+pub fn get_username_uuid(username: impl AsRef<str>) -> Result<String, ApiError> {
     User::fetch(username).map(|p| p.id)
 }
+
+/// Gets the current username of the user with the UUID.
+///
+/// The uuid can be either with or without dashes, an invalid uuid is returned as [ApiError::InvalidUuid] without making a request.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::get_username_from_uuid;
+/// assert_eq!(get_username_from_uuid("7a8084cd1f444a159bb1eef8d5b535a1").unwrap(), "brecert");
+/// ```
+pub fn get_username_from_uuid(uuid: &str) -> Result<String, ApiError> {
+    User::fetch_by_uuid(uuid).map(|user| user.name)
+}
+
+/// Gets the UUID of the user who was using the username at the given time.
+///
+/// `timestamp` is a unix timestamp in **seconds**, not milliseconds.
+/// See [User::fetch_at_time] for the limits of this lookup.
+///
+/// The username is checked with [validate_username] first, an invalid username is returned as [ApiError::InvalidUsername] without making a request.
+/// Accepts anything which can be borrowed as a string, such as a [ValidUsername].
+pub fn get_username_uuid_at(username: impl AsRef<str>, timestamp: u64) -> Result<String, ApiError> {
+    let username = username.as_ref();
+    validate_username(username)?;
+    User::fetch_at_time(username, timestamp).map(|user| user.id)
+}
+
+/// Gets the UUID of the username asynchronously
+#[cfg(feature = "async")]
+pub async fn get_username_uuid_async(username: &str) -> Result<String, ApiError> {
+    User::fetch_async(username).await.map(|p| p.id)
+}
+
+/// Checks if a username is not currently in use.
+///
+/// Usernames which fail [validate_username] can never be claimed, so `Ok(false)` is returned for them without making a request.
+/// Accepts anything which can be borrowed as a string, such as a [ValidUsername].
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::check_name_availability;
+/// assert_eq!(check_name_availability("brecert").unwrap(), false);
+/// assert_eq!(check_name_availability("ブリー").unwrap(), false);
+/// ```
+pub fn check_name_availability(username: impl AsRef<str>) -> Result<bool, ApiError> {
+    let username = username.as_ref();
+    if validate_username(username).is_err() {
+        return Ok(false);
+    }
+
+    match User::fetch(username) {
+        Ok(_) => Ok(false),
+        Err(err) if err.is_not_found() => Ok(true),
+        Err(err) => Err(err),
+    }
+}
+
+/// Names reserved by Mojang and Microsoft, which can not be claimed even though no user has them.
+///
+/// Mojang does not publish its list of banned or reserved names, and checking a name against it requires
+/// an authenticated request from the account changing its name, so this list is not exhaustive.
+pub const RESERVED_NAMES: [&str; 6] = [
+    "mojang",
+    "minecraft",
+    "microsoft",
+    "mojangstudios",
+    "xbox",
+    "realms",
+];
+
+/// Checks if a username is one of the [RESERVED_NAMES], ignoring case.
+///
+/// [check_name_availability] does not know about reserved names, so this can be used to screen names before checking them.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::is_reserved_name;
+/// assert!(is_reserved_name("Mojang"));
+/// assert!(!is_reserved_name("brecert"));
+/// ```
+pub fn is_reserved_name(username: &str) -> bool {
+    RESERVED_NAMES
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(username))
+}
+
+/// Formats a user's UUID with dashes, such as for logs or display.
+///
+/// This is the same as [format_uuid], the UUID is validated and can be either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::format_uuid_hyphenated;
+/// assert_eq!(
+///     format_uuid_hyphenated("7a8084cd1f444a159bb1eef8d5b535a1").unwrap(),
+///     "7a8084cd-1f44-4a15-9bb1-eef8d5b535a1"
+/// );
+/// ```
+pub fn format_uuid_hyphenated(raw: &str) -> Result<String, UuidError> {
+    format_uuid(raw)
+}
+
+/// Formats a user's UUID without dashes, as used by the Mojang api.
+///
+/// This is the same as [strip_uuid_dashes], the UUID is validated and can be either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::strip_uuid_hyphens;
+/// assert_eq!(
+///     strip_uuid_hyphens("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1").unwrap(),
+///     "7a8084cd1f444a159bb1eef8d5b535a1"
+/// );
+/// ```
+pub fn strip_uuid_hyphens(uuid: &str) -> Result<String, UuidError> {
+    strip_uuid_dashes(uuid)
+}
+
+/// Computes the UUID an offline mode server gives to a username.
+///
+/// This matches `UUID.nameUUIDFromBytes("OfflinePlayer:" + username)` in Java, which is a version 3 UUID.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::offline_uuid;
+/// assert_eq!(offline_uuid("Notch"), "b50ad385829d3141a2167e7d7539ba7f");
+/// ```
+pub fn offline_uuid(username: &str) -> String {
+    let mut hash = Md5::digest(format!("OfflinePlayer:{}", username).as_bytes());
+    // Set the version to 3 and the variant to RFC 4122
+    hash[6] = (hash[6] & 0x0f) | 0x30;
+    hash[8] = (hash[8] & 0x3f) | 0x80;
+    format!("{:x}", hash)
+}
+
+/// The most usernames Mojang allows in a single [get_uuids_from_usernames] request.
+pub const MAX_USERNAMES_PER_REQUEST: usize = 10;
+
+/// The delay [get_uuids_from_usernames_all] waits between requests.
+pub const DEFAULT_REQUEST_DELAY: Duration = Duration::from_millis(100);
+
 /// Gets a list of [User]s from a list of usernames in a single request
 ///
 /// Invalid usernames will be skipped in the result, and will not error
 ///
-/// Limited to 10 per request
+/// **Limited to 10 usernames per request** ([MAX_USERNAMES_PER_REQUEST]), Mojang rejects requests with more.
+/// Use [get_uuids_from_usernames_all] to fetch more than 10 usernames.
 pub fn get_uuids_from_usernames(usernames: &[&str]) -> Result<Vec<User>, ApiError> {
     let url = "https://api.mojang.com/profiles/minecraft";
-    Ok(post(url, &usernames)?.json()?)
+    parse_json(&post(url, &usernames)?)
+}
+
+/// Gets a list of [User]s from any number of usernames.
+///
+/// The usernames are split into requests of 10 ([MAX_USERNAMES_PER_REQUEST]),
+/// waiting [DEFAULT_REQUEST_DELAY] between each request to avoid being rate limited.
+///
+/// Invalid usernames will be skipped in the result, and will not error
+pub fn get_uuids_from_usernames_all(usernames: &[&str]) -> Result<Vec<User>, ApiError> {
+    get_uuids_from_usernames_all_with_delay(usernames, DEFAULT_REQUEST_DELAY)
+}
+
+/// Gets a list of [User]s from any number of usernames, waiting `delay` between each request.
+///
+/// See [get_uuids_from_usernames_all].
+pub fn get_uuids_from_usernames_all_with_delay(
+    usernames: &[&str],
+    delay: Duration,
+) -> Result<Vec<User>, ApiError> {
+    let mut users = Vec::with_capacity(usernames.len());
+
+    for (i, chunk) in usernames.chunks(MAX_USERNAMES_PER_REQUEST).enumerate() {
+        if i > 0 {
+            thread::sleep(delay);
+        }
+        users.extend(get_uuids_from_usernames(chunk)?);
+    }
+
+    Ok(users)
+}
+
+/// Gets the UUID of each username, keeping a separate result for every username so one failure does not fail the rest.
+///
+/// The results are keyed by the lowercased username. Usernames are checked with [validate_username] first,
+/// and a username which does not belong to any user gets a `404` [ApiError::Request].
+///
+/// The usernames are split into requests of 10 ([MAX_USERNAMES_PER_REQUEST]), waiting [DEFAULT_REQUEST_DELAY] between each request.
+/// If a request fails, each username in it is fetched on its own with [get_username_uuid] to find which failed.
+pub fn get_username_uuid_bulk(usernames: &[&str]) -> HashMap<String, Result<String, ApiError>> {
+    let mut results = HashMap::with_capacity(usernames.len());
+    let mut valid = Vec::with_capacity(usernames.len());
+
+    for &username in usernames {
+        match validate_username(username) {
+            Ok(()) => valid.push(username),
+            Err(err) => {
+                results.insert(username.to_lowercase(), Err(err.into()));
+            }
+        }
+    }
+
+    for (i, chunk) in valid.chunks(MAX_USERNAMES_PER_REQUEST).enumerate() {
+        if i > 0 {
+            thread::sleep(DEFAULT_REQUEST_DELAY);
+        }
+
+        match get_uuids_from_usernames(chunk) {
+            Ok(users) => {
+                let mut found: HashMap<String, String> = users
+                    .into_iter()
+                    .map(|user| (user.name.to_lowercase(), user.id))
+                    .collect();
+                for username in chunk {
+                    let key = username.to_lowercase();
+                    let result = found
+                        .remove(&key)
+                        .ok_or_else(|| ApiError::from_status(404, "Not Found"));
+                    results.insert(key, result);
+                }
+            }
+            Err(err) => {
+                log::warn!(
+                    "bulk uuid request failed, fetching usernames individually: {}",
+                    err
+                );
+                for &username in chunk {
+                    results.insert(username.to_lowercase(), get_username_uuid(username));
+                }
+            }
+        }
+    }
+
+    results
+}
+
+/// A [User] along with when it was fetched, useful for knowing when to refetch cached users.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserWithTimestamp {
+    /// The fetched user.
+    pub user: User,
+
+    /// When the user was fetched.
+    pub fetched_at: SystemTime,
+}
+
+impl UserWithTimestamp {
+    /// Returns if the user was fetched more than `max_age` ago.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use minecraft_utils::mojang_api::user::{User, UserWithTimestamp};
+    /// # use std::time::{Duration, SystemTime};
+    /// let user = UserWithTimestamp {
+    ///     user: User {
+    ///         id: String::from("7a8084cd1f444a159bb1eef8d5b535a1"),
+    ///         name: String::from("brecert"),
+    ///     },
+    ///     fetched_at: SystemTime::now() - Duration::from_secs(120),
+    /// };
+    ///
+    /// assert!(user.is_stale(Duration::from_secs(60)));
+    /// assert!(!user.is_stale(Duration::from_secs(300)));
+    /// ```
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.fetched_at.elapsed().is_ok_and(|age| age > max_age)
+    }
+}
+
+/// All the data for a player, their [User], [Profile], and username history.
+///
+/// Displays as `name (uuid)`, the same as [User].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    /// The player's current username and UUID.
+    pub user: User,
+
+    /// The player's profile, with their textures.
+    pub profile: Profile,
+
+    /// The player's username history, sorted chronologically (oldest first).
+    ///
+    /// Mojang retired the username history endpoint in 2022, so this is empty unless the history could still be fetched,
+    /// see [Profile::fetch_username_history].
+    pub history: Vec<UsernameEntry>,
+}
+
+impl Player {
+    /// Fetches the user, profile, and username history of a player from either a UUID or a username.
+    ///
+    /// Strings longer than 16 characters (the longest a username can be) are treated as UUIDs,
+    /// the same as [Profile::try_from], and usernames are checked with [validate_username] first.
+    ///
+    /// The username history is fetched on a best-effort basis, failing to fetch it leaves [Player::history] empty
+    /// instead of failing the whole fetch.
+    pub fn fetch(username_or_uuid: &str) -> Result<Self, ApiError> {
+        let profile = if username_or_uuid.replace('-', "").len() > 16 {
+            Profile::fetch(username_or_uuid)?
+        } else {
+            validate_username(username_or_uuid)?;
+            Profile::fetch(&User::fetch(username_or_uuid)?.id)?
+        };
+        let history = Profile::fetch_username_history(&profile.id).unwrap_or_else(|err| {
+            log::debug!(
+                "could not fetch username history of {}: {}",
+                profile.id,
+                err
+            );
+            Vec::new()
+        });
+
+        Ok(Player {
+            user: User {
+                id: profile.id.clone(),
+                name: profile.name.clone(),
+            },
+            profile,
+            history,
+        })
+    }
+
+    /// Returns if the player's current username is `name`, ignoring case.
+    pub fn is_currently_named(&self, name: &str) -> bool {
+        self.user.name.eq_ignore_ascii_case(name)
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.user.fmt(f)
+    }
+}
+
+/// Gets a list of [User]s from a list of usernames in a single request, along with when they were fetched.
+///
+/// See [get_uuids_from_usernames].
+pub fn get_uuid_batch_with_timestamps(
+    usernames: &[&str],
+) -> Result<Vec<UserWithTimestamp>, ApiError> {
+    let users = get_uuids_from_usernames(usernames)?;
+    let fetched_at = SystemTime::now();
+
+    Ok(users
+        .into_iter()
+        .map(|user| UserWithTimestamp { user, fetched_at })
+        .collect())
+}
+
+/// Gets a list of [User]s from a list of usernames in a single request asynchronously
+///
+/// Invalid usernames will be skipped in the result, and will not error
+///
+/// Limited to 10 per request
+#[cfg(feature = "async")]
+pub async fn get_uuids_from_usernames_async(usernames: &[&str]) -> Result<Vec<User>, ApiError> {
+    let url = "https://api.mojang.com/profiles/minecraft";
+    parse_json_async(post_async(url, &usernames).await?).await
+}
+
+/// A single rule a username must follow, see [validate_username_with_rules].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsernameRule {
+    /// The username must not be empty.
+    NoEmptyName,
+
+    /// The username must be at most this many characters long.
+    MaxLength(usize),
+
+    /// The username must be at least this many characters long.
+    MinLength(usize),
+
+    /// The username must only contain ascii letters, digits, and underscores.
+    AsciiAlphanumericOrUnderscore,
+
+    /// The username must not start with an underscore.
+    NoLeadingUnderscore,
+
+    /// The username must not end with an underscore.
+    NoTrailingUnderscore,
+}
+
+impl UsernameRule {
+    /// The rules checked by [validate_username].
+    pub const LENIENT: &'static [UsernameRule] = &[
+        UsernameRule::NoEmptyName,
+        UsernameRule::MaxLength(16),
+        UsernameRule::AsciiAlphanumericOrUnderscore,
+    ];
+
+    /// The rules checked by [validate_username_strict].
+    pub const STRICT: &'static [UsernameRule] = &[
+        UsernameRule::NoEmptyName,
+        UsernameRule::MaxLength(16),
+        UsernameRule::AsciiAlphanumericOrUnderscore,
+        UsernameRule::MinLength(3),
+        UsernameRule::NoLeadingUnderscore,
+        UsernameRule::NoTrailingUnderscore,
+    ];
+
+    /// Checks if the username follows this rule.
+    pub fn check(&self, username: &str) -> Result<(), UsernameError> {
+        match *self {
+            UsernameRule::NoEmptyName if username.is_empty() => Err(UsernameError::Empty),
+            UsernameRule::MaxLength(max) if username.chars().count() > max => {
+                Err(UsernameError::TooLong)
+            }
+            UsernameRule::MinLength(min) if username.chars().count() < min => {
+                Err(UsernameError::TooShort)
+            }
+            UsernameRule::AsciiAlphanumericOrUnderscore => username
+                .chars()
+                .find(|&ch| !ch.is_ascii_alphanumeric() && ch != '_')
+                .map_or(Ok(()), |ch| Err(UsernameError::InvalidCharacter(ch))),
+            UsernameRule::NoLeadingUnderscore if username.starts_with('_') => {
+                Err(UsernameError::InvalidUnderscorePosition)
+            }
+            UsernameRule::NoTrailingUnderscore if username.ends_with('_') => {
+                Err(UsernameError::InvalidUnderscorePosition)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks a username against each rule in order, returning the first rule it breaks.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::error::UsernameError;
+/// # use minecraft_utils::mojang_api::user::{validate_username_with_rules, UsernameRule};
+/// let rules = [UsernameRule::NoEmptyName, UsernameRule::MaxLength(10), UsernameRule::NoLeadingUnderscore];
+///
+/// assert_eq!(validate_username_with_rules("_brecert", &[UsernameRule::MaxLength(10)]), Ok(()));
+/// assert_eq!(
+///     validate_username_with_rules("_brecert", &rules),
+///     Err(UsernameError::InvalidUnderscorePosition)
+/// );
+/// assert_eq!(validate_username_with_rules("", &rules), Err(UsernameError::Empty));
+/// ```
+pub fn validate_username_with_rules(
+    username: &str,
+    rules: &[UsernameRule],
+) -> Result<(), UsernameError> {
+    rules.iter().try_for_each(|rule| rule.check(username))
 }
 
 /// Checks if a username is a valid username that the api may return.
@@ -54,26 +599,463 @@ pub fn get_uuids_from_usernames(usernames: &[&str]) -> Result<Vec<User>, ApiErro
 /// assert_eq!(validate_username(""), Err(UsernameError::Empty));
 /// ```
 pub fn validate_username(username: &str) -> Result<(), UsernameError> {
-    if username.len() == 0 {
-        return Err(UsernameError::Empty);
+    validate_username_with_rules(username, UsernameRule::LENIENT)
+}
+
+/// Checks if a username is valid under the modern username rules.
+///
+/// This is the same as [validate_username], but additionally rejects usernames shorter than 3 characters,
+/// and usernames which start or end with an underscore.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::error::UsernameError;
+/// # use minecraft_utils::mojang_api::user::validate_username_strict;
+/// assert_eq!(validate_username_strict("brecert"), Ok(()));
+/// assert_eq!(validate_username_strict("ab"), Err(UsernameError::TooShort));
+/// assert_eq!(validate_username_strict("_bad"), Err(UsernameError::InvalidUnderscorePosition));
+/// assert_eq!(validate_username_strict(""), Err(UsernameError::Empty));
+/// ```
+pub fn validate_username_strict(username: &str) -> Result<(), UsernameError> {
+    validate_username_with_rules(username, UsernameRule::STRICT)
+}
+
+/// A username which has been checked with [validate_username].
+///
+/// The lookups which take a username, such as [get_username_uuid], [get_username_uuid_at], and [check_name_availability],
+/// accept a `&ValidUsername` in place of a `&str`.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::error::UsernameError;
+/// # use minecraft_utils::mojang_api::user::ValidUsername;
+/// let username = ValidUsername::new("brecert").unwrap();
+/// assert_eq!(&*username, "brecert");
+///
+/// assert_eq!("".parse::<ValidUsername>(), Err(UsernameError::Empty));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct ValidUsername(String);
+
+impl ValidUsername {
+    /// Validates the username, returning it wrapped if it is valid.
+    pub fn new(username: &str) -> Result<Self, UsernameError> {
+        validate_username(username)?;
+        Ok(ValidUsername(username.to_string()))
+    }
+
+    /// Returns the username as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ValidUsername {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for ValidUsername {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ValidUsername {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ValidUsername {
+    type Err = UsernameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ValidUsername::new(s)
+    }
+}
+
+impl TryFrom<String> for ValidUsername {
+    type Error = UsernameError;
+
+    fn try_from(username: String) -> Result<Self, Self::Error> {
+        validate_username(&username)?;
+        Ok(ValidUsername(username))
+    }
+}
+
+impl From<ValidUsername> for String {
+    fn from(username: ValidUsername) -> Self {
+        username.0
+    }
+}
+
+/// A username which compares case-insensitively, as Minecraft usernames do.
+///
+/// The username is checked with [validate_username] and stored in lowercase,
+/// so it can be used as a key in maps without `"brecert"` and `"BRECERT"` being different players.
+///
+/// Lookups such as [get_username_uuid] and [User::fetch] accept a [Username], and [User::username] produces one.
+/// Fields like [User::name] and [TexturesEntry::profile_name](crate::mojang_api::profile::TexturesEntry::profile_name)
+/// are left as [String], since they hold the name with the casing the api returned, which a [Username] would lose.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::user::Username;
+/// let username = Username::new("BreCert").unwrap();
+///
+/// assert_eq!(&*username, "brecert");
+/// assert_eq!(username, Username::new("BRECERT").unwrap());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct Username(String);
+
+impl Username {
+    /// Validates the username, returning it in lowercase if it is valid.
+    pub fn new(username: &str) -> Result<Self, UsernameError> {
+        Ok(ValidUsername::new(username)?.into())
+    }
+
+    /// Returns the lowercase username as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Username {
+    fn as_ref(&self) -> &str {
+        &self.0
     }
+}
+
+impl Deref for Username {
+    type Target = str;
 
-    if username.len() > 16 {
-        return Err(UsernameError::TooLong);
+    fn deref(&self) -> &str {
+        &self.0
     }
+}
 
-    username
-        .chars()
-        .find(|&ch| !ch.is_ascii_alphanumeric() && ch != '_')
-        .map_or(Ok(()), |ch| Err(UsernameError::InvalidCharacter(ch)))
+impl fmt::Display for Username {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Username {
+    type Err = UsernameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Username::new(s)
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = UsernameError;
+
+    fn try_from(username: String) -> Result<Self, Self::Error> {
+        Ok(ValidUsername::try_from(username)?.into())
+    }
+}
+
+impl From<ValidUsername> for Username {
+    fn from(username: ValidUsername) -> Self {
+        let mut username = String::from(username);
+        username.make_ascii_lowercase();
+        Username(username)
+    }
+}
+
+impl From<Username> for String {
+    fn from(username: Username) -> Self {
+        username.0
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::mojang_api::client::MockClient;
+
+    #[test]
+    fn parse_user() {
+        assert_eq!(
+            "brecert (7a8084cd1f444a159bb1eef8d5b535a1)".parse(),
+            Ok(User {
+                id: "7a8084cd1f444a159bb1eef8d5b535a1".into(),
+                name: "brecert".into(),
+            })
+        );
+        assert_eq!(
+            "brecert".parse::<User>(),
+            Err(UserParseError::InvalidFormat)
+        );
+        assert_eq!(
+            "brecert ()".parse::<User>(),
+            Err(UserParseError::InvalidFormat)
+        );
+        assert_eq!(
+            "bre cert (7a8084cd1f444a159bb1eef8d5b535a1)".parse::<User>(),
+            Err(UserParseError::InvalidUsername(
+                UsernameError::InvalidCharacter(' ')
+            ))
+        );
+    }
+
+    #[test]
+    fn offline_uuids() {
+        assert_eq!(offline_uuid("brecert"), "da90b5d7ba293f41aff921b5183a0699");
+        assert_eq!(offline_uuid("Notch"), "b50ad385829d3141a2167e7d7539ba7f");
+    }
+
+    #[test]
+    fn validate_short_usernames() {
+        assert_eq!(validate_username("a"), Ok(()));
+        assert_eq!(validate_username("ab"), Ok(()));
+        assert_eq!(validate_username("abc"), Ok(()));
+    }
+
+    #[test]
+    fn validate_strict_length_boundaries() {
+        assert_eq!(validate_username_strict(""), Err(UsernameError::Empty));
+        assert_eq!(validate_username_strict("a"), Err(UsernameError::TooShort));
+        assert_eq!(validate_username_strict("ab"), Err(UsernameError::TooShort));
+        assert_eq!(validate_username_strict("abc"), Ok(()));
+        assert_eq!(validate_username_strict("abcdefghijklmnop"), Ok(()));
+        assert_eq!(
+            validate_username_strict("abcdefghijklmnopq"),
+            Err(UsernameError::TooLong)
+        );
+        assert_eq!(
+            validate_username_strict("a-"),
+            Err(UsernameError::InvalidCharacter('-'))
+        );
+    }
+
+    #[test]
+    fn valid_username_serde() {
+        let username: ValidUsername = serde_json::from_str(r#""brecert""#).unwrap();
+        assert_eq!(username.as_str(), "brecert");
+        assert_eq!(serde_json::to_string(&username).unwrap(), r#""brecert""#);
+
+        assert!(serde_json::from_str::<ValidUsername>(r#""not valid""#).is_err());
+    }
+
+    #[test]
+    fn username_case_insensitive() {
+        let mut uuids = HashMap::new();
+        uuids.insert(
+            Username::new("brecert").unwrap(),
+            "7a8084cd1f444a159bb1eef8d5b535a1",
+        );
+
+        assert_eq!(
+            uuids.get(&Username::new("BRECERT").unwrap()),
+            Some(&"7a8084cd1f444a159bb1eef8d5b535a1")
+        );
+        assert!(Username::new("Alpha").unwrap() < Username::new("beta").unwrap());
+
+        let username: Username = serde_json::from_str(r#""BreCert""#).unwrap();
+        assert_eq!(serde_json::to_string(&username).unwrap(), r#""brecert""#);
+    }
+
+    #[test]
+    fn get_username_uuid_with_username() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://api.mojang.com/users/profiles/minecraft/brecert",
+                200,
+                r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }"#,
+            )
+            .install();
+
+        let username = Username::new("BreCert").unwrap();
+        assert_eq!(
+            get_username_uuid(&username).unwrap(),
+            "7a8084cd1f444a159bb1eef8d5b535a1"
+        );
+
+        let username = ValidUsername::new("brecert").unwrap();
+        assert_eq!(
+            get_username_uuid(&username).unwrap(),
+            "7a8084cd1f444a159bb1eef8d5b535a1"
+        );
+    }
+
+    #[test]
+    fn lookups_accept_valid_username() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://api.mojang.com/users/profiles/minecraft/brecert?at=0",
+                200,
+                r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }"#,
+            )
+            .install();
+
+        let taken = ValidUsername::new("brecert").unwrap();
+        let free = ValidUsername::new("not_taken").unwrap();
+
+        assert_eq!(
+            get_username_uuid_at(&taken, 0).unwrap(),
+            "7a8084cd1f444a159bb1eef8d5b535a1"
+        );
+        assert!(check_name_availability(&free).unwrap());
+    }
+
+    #[test]
+    fn get_uuids_all_in_batches() {
+        let url = "https://api.mojang.com/profiles/minecraft";
+        let guard = MockClient::new()
+            .respond(
+                url,
+                200,
+                r#"[{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }]"#,
+            )
+            .install();
+
+        let names: Vec<String> = (0..25).map(|i| format!("user{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let users = get_uuids_from_usernames_all_with_delay(&names, Duration::ZERO).unwrap();
+
+        // 25 usernames are sent as requests of 10, 10, and 5, and each response is merged in order.
+        assert_eq!(guard.requested(), [url, url, url]);
+        assert_eq!(users.len(), 3);
+        assert!(users.iter().all(|user| user.name == "brecert"));
+    }
+
+    #[test]
+    fn get_username_uuid_bulk_per_item() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://api.mojang.com/profiles/minecraft",
+                200,
+                r#"[{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }]"#,
+            )
+            .install();
+
+        let results = get_username_uuid_bulk(&["BreCert", "not valid", "missing_user"]);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results["brecert"].as_deref().unwrap(),
+            "7a8084cd1f444a159bb1eef8d5b535a1"
+        );
+        assert!(matches!(
+            results["not valid"],
+            Err(ApiError::InvalidUsername(_))
+        ));
+        assert!(results["missing_user"].as_ref().unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn get_username_uuid_at_validates() {
+        assert!(matches!(
+            get_username_uuid_at("not valid", 0),
+            Err(ApiError::InvalidUsername(UsernameError::InvalidCharacter(
+                ' '
+            )))
+        ));
+    }
+
+    #[test]
+    fn player_is_currently_named() {
+        let profile = Profile::from_json(
+            r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert", "properties": [] }"#,
+        )
+        .unwrap();
+        let player = Player {
+            user: User {
+                id: profile.id.clone(),
+                name: profile.name.clone(),
+            },
+            profile,
+            history: Vec::new(),
+        };
+
+        assert!(player.is_currently_named("BreCert"));
+        assert!(!player.is_currently_named("bree"));
+        assert_eq!(
+            player.to_string(),
+            "brecert (7a8084cd1f444a159bb1eef8d5b535a1)"
+        );
+    }
+
+    #[test]
+    fn player_fetch_without_history() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://sessionserver.mojang.com/session/minecraft/profile/7a8084cd1f444a159bb1eef8d5b535a1",
+                200,
+                r#"{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert", "properties": [] }"#,
+            )
+            .install();
+
+        let player = Player::fetch("7a8084cd1f444a159bb1eef8d5b535a1").unwrap();
+
+        assert_eq!(player.user.name, "brecert");
+        assert!(player.history.is_empty());
+    }
+
+    #[test]
+    fn strict_underscore_position() {
+        assert_eq!(
+            validate_username_strict("_leading"),
+            Err(UsernameError::InvalidUnderscorePosition)
+        );
+        assert_eq!(
+            validate_username_strict("trailing_"),
+            Err(UsernameError::InvalidUnderscorePosition)
+        );
+        assert_eq!(validate_username_strict("valid_middle"), Ok(()));
+
+        // The lenient rules still allow them.
+        assert_eq!(validate_username("_leading"), Ok(()));
+        assert_eq!(validate_username("trailing_"), Ok(()));
+    }
+
+    #[test]
+    fn length_rules_count_characters() {
+        let rules = [UsernameRule::MinLength(3), UsernameRule::MaxLength(4)];
+
+        // 3 characters, but 9 bytes.
+        assert_eq!(validate_username_with_rules("ブリー", &rules), Ok(()));
+        assert_eq!(
+            validate_username_with_rules("ブリ", &rules),
+            Err(UsernameError::TooShort)
+        );
+        assert_eq!(
+            validate_username_with_rules("ブリーブリ", &rules),
+            Err(UsernameError::TooLong)
+        );
+    }
+
+    #[test]
+    fn get_username_from_uuid_validates() {
+        assert!(matches!(
+            get_username_from_uuid("not a uuid"),
+            Err(ApiError::InvalidUuid(UuidError::InvalidLength(10)))
+        ));
+    }
 
     #[test]
     fn get_uuids() {
+        let _guard = MockClient::new()
+            .respond(
+                "https://api.mojang.com/profiles/minecraft",
+                200,
+                r#"[
+                    { "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" },
+                    { "id": "156b251b12e04829a130a61b53ba7720", "name": "MHF_Present1" },
+                    { "id": "f1eb7cade2c04e9e8aad1eae21d5fd95", "name": "MHF_Present2" }
+                ]"#,
+            )
+            .install();
+
         let uuids = get_uuids_from_usernames(&["brecert", "MHF_Present1", "MHF_Present2"]).unwrap();
         assert_eq!(
             uuids,
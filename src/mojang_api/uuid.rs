@@ -0,0 +1,124 @@
+use crate::mojang_api::error::UuidError;
+
+/// The positions of the dashes in a hyphenated UUID.
+const DASH_POSITIONS: [usize; 4] = [8, 13, 18, 23];
+
+/// Parses a UUID with or without dashes into its 32 lowercase hex digits.
+fn parse_raw(uuid: &str) -> Result<String, UuidError> {
+    let len = uuid.chars().count();
+    let hyphenated = match len {
+        32 => false,
+        36 => true,
+        _ => return Err(UuidError::InvalidLength(len)),
+    };
+
+    let mut raw = String::with_capacity(32);
+    for (i, ch) in uuid.chars().enumerate() {
+        if hyphenated && DASH_POSITIONS.contains(&i) {
+            if ch != '-' {
+                return Err(UuidError::MisplacedDash);
+            }
+        } else if ch == '-' {
+            return Err(UuidError::MisplacedDash);
+        } else if ch.is_ascii_hexdigit() {
+            raw.push(ch.to_ascii_lowercase());
+        } else {
+            return Err(UuidError::InvalidCharacter(ch));
+        }
+    }
+
+    Ok(raw)
+}
+
+/// Formats a UUID with dashes, as used by Java and most databases.
+///
+/// The UUID can be either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::uuid::format_uuid;
+/// assert_eq!(
+///     format_uuid("7a8084cd1f444a159bb1eef8d5b535a1").unwrap(),
+///     "7a8084cd-1f44-4a15-9bb1-eef8d5b535a1"
+/// );
+/// assert!(format_uuid("not a uuid").is_err());
+/// ```
+pub fn format_uuid(raw: &str) -> Result<String, UuidError> {
+    let raw = parse_raw(raw)?;
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &raw[..8],
+        &raw[8..12],
+        &raw[12..16],
+        &raw[16..20],
+        &raw[20..]
+    ))
+}
+
+/// Formats a UUID without dashes, as used by the Mojang api.
+///
+/// The UUID can be either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::uuid::strip_uuid_dashes;
+/// assert_eq!(
+///     strip_uuid_dashes("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1").unwrap(),
+///     "7a8084cd1f444a159bb1eef8d5b535a1"
+/// );
+/// ```
+pub fn strip_uuid_dashes(uuid: &str) -> Result<String, UuidError> {
+    parse_raw(uuid)
+}
+
+/// Checks if a string is a valid UUID, either with or without dashes.
+///
+/// ## Example
+/// ```rust
+/// # use minecraft_utils::mojang_api::error::UuidError;
+/// # use minecraft_utils::mojang_api::uuid::validate_uuid;
+/// assert_eq!(validate_uuid("7a8084cd1f444a159bb1eef8d5b535a1"), Ok(()));
+/// assert_eq!(validate_uuid("7a8084cd-1f44-4a15-9bb1-eef8d5b535a1"), Ok(()));
+/// assert_eq!(
+///     validate_uuid("7a8084cd1f444a159bb1eef8d5b535a"),
+///     Err(UuidError::InvalidLength(31))
+/// );
+/// ```
+pub fn validate_uuid(uuid: &str) -> Result<(), UuidError> {
+    parse_raw(uuid).map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_raw(""), Err(UuidError::InvalidLength(0)));
+        assert_eq!(
+            parse_raw("7a8084cd1f444a159bb1eef8d5b535ag"),
+            Err(UuidError::InvalidCharacter('g'))
+        );
+        assert_eq!(
+            parse_raw("7a8084cd1-f44-4a15-9bb1-eef8d5b535a1"),
+            Err(UuidError::MisplacedDash)
+        );
+        assert_eq!(
+            parse_raw("7a8084cd-1f444a159bb1eef8d5b535a1"),
+            Err(UuidError::InvalidLength(33))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let raw = "7a8084cd1f444a159bb1eef8d5b535a1";
+        let hyphenated = format_uuid(raw).unwrap();
+
+        assert_eq!(strip_uuid_dashes(&hyphenated).unwrap(), raw);
+        assert_eq!(format_uuid(&hyphenated).unwrap(), hyphenated);
+        assert_eq!(
+            strip_uuid_dashes("7A8084CD1F444A159BB1EEF8D5B535A1").unwrap(),
+            raw
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mojang_api::client::{parse_json, post, post_empty};
+use crate::mojang_api::error::ApiError;
+use crate::mojang_api::user::User;
+
+const AUTH_SERVER: &str = "https://authserver.mojang.com";
+
+#[derive(Serialize)]
+struct Agent {
+    name: &'static str,
+    version: u8,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticateRequest<'a> {
+    agent: Agent,
+    username: &'a str,
+    password: &'a str,
+    client_token: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenRequest<'a> {
+    access_token: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_token: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct SignoutRequest<'a> {
+    username: &'a str,
+    password: &'a str,
+}
+
+/// The response from [authenticate] or [refresh].
+///
+/// The tokens are redacted when formatted with [Debug](fmt::Debug), so the response can be logged without leaking them.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthResponse {
+    /// The token used to authenticate as the account, such as with [session::join](crate::mojang_api::session::join).
+    pub access_token: String,
+
+    /// The client token the access token is bound to.
+    pub client_token: String,
+
+    /// The profiles the account owns, this is only returned by [authenticate].
+    #[serde(default)]
+    pub available_profiles: Vec<User>,
+
+    /// The profile currently selected, this is [None] if the account doesn't own the game.
+    pub selected_profile: Option<User>,
+}
+
+impl fmt::Debug for AuthResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthResponse")
+            .field("access_token", &"<redacted>")
+            .field("client_token", &"<redacted>")
+            .field("available_profiles", &self.available_profiles)
+            .field("selected_profile", &self.selected_profile)
+            .finish()
+    }
+}
+
+/// Authenticates with a username (or email) and password.
+///
+/// `client_token` should be a random identifier which is kept the same between authentications for a client,
+/// as authenticating invalidates every access token previously given for that client token.
+///
+/// The password is sent to Mojang, and should never be logged or stored.
+pub fn authenticate(
+    username: &str,
+    password: &str,
+    client_token: &str,
+) -> Result<AuthResponse, ApiError> {
+    let url = format!("{}/authenticate", AUTH_SERVER);
+    let body = AuthenticateRequest {
+        agent: Agent {
+            name: "Minecraft",
+            version: 1,
+        },
+        username,
+        password,
+        client_token,
+    };
+    parse_json(&post(url, &body)?)
+}
+
+/// Refreshes an access token, invalidating the given one.
+///
+/// `client_token` must be the same client token used to get the access token.
+pub fn refresh(access_token: &str, client_token: &str) -> Result<AuthResponse, ApiError> {
+    let url = format!("{}/refresh", AUTH_SERVER);
+    let body = TokenRequest {
+        access_token,
+        client_token: Some(client_token),
+    };
+    parse_json(&post(url, &body)?)
+}
+
+/// Checks if an access token is still usable.
+///
+/// An invalid token is returned as an [ApiError::Request] with the status `403`, the token should then be refreshed with [refresh].
+pub fn validate(access_token: &str) -> Result<(), ApiError> {
+    let url = format!("{}/validate", AUTH_SERVER);
+    let body = TokenRequest {
+        access_token,
+        client_token: None,
+    };
+    post_empty(url, &body)
+}
+
+/// Invalidates every access token for the account.
+///
+/// The password is sent to Mojang, and should never be logged or stored.
+pub fn signout(username: &str, password: &str) -> Result<(), ApiError> {
+    let url = format!("{}/signout", AUTH_SERVER);
+    post_empty(url, &SignoutRequest { username, password })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mojang_api::client::MockClient;
+    use serde_json::json;
+
+    const AUTH_RESPONSE: &str = r#"{
+        "accessToken": "token",
+        "clientToken": "client",
+        "availableProfiles": [{ "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }],
+        "selectedProfile": { "id": "7a8084cd1f444a159bb1eef8d5b535a1", "name": "brecert" }
+    }"#;
+
+    #[test]
+    fn request_bodies() {
+        let authenticate = AuthenticateRequest {
+            agent: Agent {
+                name: "Minecraft",
+                version: 1,
+            },
+            username: "brecert",
+            password: "hunter2",
+            client_token: "client",
+        };
+        assert_eq!(
+            serde_json::to_value(authenticate).unwrap(),
+            json!({
+                "agent": { "name": "Minecraft", "version": 1 },
+                "username": "brecert",
+                "password": "hunter2",
+                "clientToken": "client",
+            })
+        );
+
+        let refresh = TokenRequest {
+            access_token: "token",
+            client_token: Some("client"),
+        };
+        assert_eq!(
+            serde_json::to_value(refresh).unwrap(),
+            json!({ "accessToken": "token", "clientToken": "client" })
+        );
+
+        // The client token is left out entirely when validating.
+        let validate = TokenRequest {
+            access_token: "token",
+            client_token: None,
+        };
+        assert_eq!(
+            serde_json::to_value(validate).unwrap(),
+            json!({ "accessToken": "token" })
+        );
+    }
+
+    #[test]
+    fn authenticate_and_refresh() {
+        let _guard = MockClient::new()
+            .respond(format!("{}/authenticate", AUTH_SERVER), 200, AUTH_RESPONSE)
+            .respond(
+                format!("{}/refresh", AUTH_SERVER),
+                200,
+                r#"{ "accessToken": "new", "clientToken": "client", "selectedProfile": null }"#,
+            )
+            .install();
+
+        let auth = authenticate("brecert", "hunter2", "client").unwrap();
+        assert_eq!(auth.access_token, "token");
+        assert_eq!(auth.available_profiles.len(), 1);
+        assert_eq!(auth.selected_profile.unwrap().name, "brecert");
+
+        let refreshed = refresh(&auth.access_token, "client").unwrap();
+        assert_eq!(refreshed.access_token, "new");
+        assert!(refreshed.available_profiles.is_empty());
+        assert_eq!(refreshed.selected_profile, None);
+    }
+
+    #[test]
+    fn debug_redacts_tokens() {
+        let auth: AuthResponse = serde_json::from_str(AUTH_RESPONSE).unwrap();
+        let debug = format!("{:?}", auth);
+
+        assert!(!debug.contains("\"token\""));
+        assert!(!debug.contains("\"client\""));
+        assert!(debug.contains("<redacted>"));
+        assert!(debug.contains("brecert"));
+    }
+
+    #[test]
+    fn authenticate_forbidden() {
+        let _guard = MockClient::new()
+            .respond(format!("{}/authenticate", AUTH_SERVER), 403, "")
+            .install();
+
+        let err = authenticate("brecert", "wrong", "client").unwrap_err();
+        assert_eq!(err.status_code(), Some(403));
+    }
+
+    #[test]
+    fn validate_no_content() {
+        let _guard = MockClient::new()
+            .respond(format!("{}/validate", AUTH_SERVER), 204, "")
+            .respond(format!("{}/signout", AUTH_SERVER), 204, "")
+            .install();
+
+        assert!(validate("token").is_ok());
+        assert!(signout("brecert", "hunter2").is_ok());
+    }
+
+    #[test]
+    fn validate_invalid_token() {
+        let _guard = MockClient::new()
+            .respond(format!("{}/validate", AUTH_SERVER), 403, "")
+            .respond(format!("{}/signout", AUTH_SERVER), 403, "")
+            .install();
+
+        assert_eq!(validate("expired").unwrap_err().status_code(), Some(403));
+        assert_eq!(
+            signout("brecert", "wrong").unwrap_err().status_code(),
+            Some(403)
+        );
+    }
+}